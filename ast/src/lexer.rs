@@ -751,12 +751,24 @@ impl Lexer {
         let start = self.position;
 
         while self.has_next() && self.current_byte() != NEWLINE {
+            // For Windows line endings (\r\n) the \r isn't part of the
+            // comment.
+            if self.current_byte() == CARRIAGE_RETURN
+                && self.next_byte() == NEWLINE
+            {
+                break;
+            }
+
             self.position += 1;
         }
 
         let comment =
             self.token_with_column(TokenKind::Comment, start, line, column);
 
+        if self.current_byte() == CARRIAGE_RETURN {
+            self.position += 1;
+        }
+
         self.advance_line();
         comment
     }
@@ -1420,6 +1432,16 @@ mod tests {
         assert_token!("# foo", Comment, "foo", 1..=1, 1..=5);
         assert_token!("# foo\nbar", Comment, "foo", 1..=1, 1..=5);
         assert_token!("# €€€", Comment, "€€€", 1..=1, 1..=5);
+        assert_token!("# foo\r\nbar", Comment, "foo", 1..=1, 1..=5);
+        assert_token!("# foo\rbar", Comment, "foo\rbar", 1..=1, 1..=9);
+    }
+
+    #[test]
+    fn test_lexer_comment_with_windows_line_endings() {
+        let mut lexer = lexer("# foo\r\nbar");
+
+        assert_eq!(lexer.next_token(), tok(Comment, "foo", 1..=1, 1..=5));
+        assert_eq!(lexer.next_token(), tok(Identifier, "bar", 2..=2, 1..=3));
     }
 
     #[test]
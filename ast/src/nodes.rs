@@ -350,6 +350,7 @@ pub struct Import {
     pub location: SourceLocation,
     pub tags: Option<BuildTags>,
     pub include: bool,
+    pub public: bool,
 }
 
 impl Node for Import {
@@ -897,6 +898,7 @@ impl Node for TypeParameters {
 pub struct MethodArgument {
     pub name: Identifier,
     pub value_type: Type,
+    pub default: Option<Expression>,
     pub location: SourceLocation,
 }
 
@@ -1580,6 +1582,10 @@ pub struct Module {
     pub expressions: Vec<TopLevelExpression>,
     pub file: PathBuf,
     pub location: SourceLocation,
+
+    /// The names of the warnings to silence for this module, specified using
+    /// comments in the form of `# inko: allow(NAME, ...)`.
+    pub allowed_warnings: Vec<Identifier>,
 }
 
 impl Node for Module {
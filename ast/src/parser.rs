@@ -7,6 +7,7 @@
 use crate::lexer::{Lexer, Token, TokenKind};
 use crate::nodes::*;
 use crate::source_location::SourceLocation;
+use std::mem::take;
 use std::path::PathBuf;
 
 /// Produces a parser error and returns from the surrounding function.
@@ -60,13 +61,20 @@ pub struct Parser {
     lexer: Lexer,
     peeked: Option<Token>,
     comments: bool,
+    allowed_warnings: Vec<Identifier>,
 }
 
 impl Parser {
     pub fn new(input: Vec<u8>, file: PathBuf) -> Self {
         let lexer = Lexer::new(input);
 
-        Self { file, lexer, comments: false, peeked: None }
+        Self {
+            file,
+            lexer,
+            comments: false,
+            peeked: None,
+            allowed_warnings: Vec::new(),
+        }
     }
 
     pub fn with_comments(input: Vec<u8>, file: PathBuf) -> Self {
@@ -77,8 +85,28 @@ impl Parser {
     }
 
     pub fn parse(&mut self) -> Result<Module, ParseError> {
+        let (module, mut errors) = self.parse_with_recovery();
+
+        if errors.is_empty() {
+            Ok(module)
+        } else {
+            Err(errors.swap_remove(0))
+        }
+    }
+
+    /// Parses the input into a module, recovering from syntax errors.
+    ///
+    /// When a top-level expression contains a syntax error, the parser skips
+    /// ahead to the next top-level definition at the start of a line (e.g.
+    /// `fn` or `class`) and continues parsing from there. This way a single
+    /// syntax error doesn't hide the errors that follow it.
+    ///
+    /// The returned module only contains the top-level expressions that were
+    /// parsed successfully.
+    pub fn parse_with_recovery(&mut self) -> (Module, Vec<ParseError>) {
         let start_loc = self.lexer.start_location();
         let mut expressions = Vec::new();
+        let mut errors = Vec::new();
 
         loop {
             let token = self.next();
@@ -88,10 +116,43 @@ impl Parser {
                 let location =
                     SourceLocation::start_end(&start_loc, &token.location);
 
-                return Ok(Module { expressions, file, location });
+                let allowed_warnings = take(&mut self.allowed_warnings);
+                let module =
+                    Module { expressions, file, location, allowed_warnings };
+
+                return (module, errors);
             }
 
-            expressions.push(self.top_level_expression(token)?);
+            match self.top_level_expression(token) {
+                Ok(expr) => expressions.push(expr),
+                Err(err) => {
+                    errors.push(err);
+                    self.skip_to_top_level_expression();
+                }
+            }
+        }
+    }
+
+    fn skip_to_top_level_expression(&mut self) {
+        loop {
+            let token = self.peek();
+
+            match token.kind {
+                TokenKind::Null => return,
+                TokenKind::Import
+                | TokenKind::Class
+                | TokenKind::Implement
+                | TokenKind::Trait
+                | TokenKind::Fn
+                | TokenKind::Let
+                    if *token.location.columns.start() == 1 =>
+                {
+                    return;
+                }
+                _ => {
+                    self.next();
+                }
+            }
         }
     }
 
@@ -129,6 +190,7 @@ impl Parser {
             return self.extern_import(start);
         }
 
+        let public = self.next_is_public();
         let path = self.import_path()?;
         let symbols = self.import_symbols()?;
         let tags = self.build_tags()?;
@@ -145,6 +207,7 @@ impl Parser {
             tags,
             location,
             include: true,
+            public,
         })))
     }
 
@@ -368,6 +431,11 @@ impl Parser {
             TokenKind::ParenOpen => self.const_group(start)?,
             TokenKind::BracketOpen => self.const_array(start)?,
             TokenKind::Comment => Expression::Comment(self.comment(start)),
+            TokenKind::Identifier
+                if self.peek().kind == TokenKind::ParenOpen =>
+            {
+                self.const_call(start)?
+            }
             TokenKind::Identifier => {
                 self.expect(TokenKind::Dot)?;
 
@@ -409,6 +477,26 @@ impl Parser {
         Ok(Expression::Group(Box::new(Group { value, location })))
     }
 
+    fn const_call(&mut self, start: Token) -> Result<Expression, ParseError> {
+        let name = Identifier::from(start);
+        let (values, args_location) = self.list(
+            TokenKind::ParenOpen,
+            TokenKind::ParenClose,
+            |parser, token| {
+                Ok(Argument::Positional(parser.const_expression(token)?))
+            },
+        )?;
+        let location =
+            SourceLocation::start_end(&name.location, &args_location);
+
+        Ok(Expression::Call(Box::new(Call {
+            receiver: None,
+            name,
+            arguments: Some(Arguments { values, location: args_location }),
+            location,
+        })))
+    }
+
     fn const_array(&mut self, start: Token) -> Result<Expression, ParseError> {
         let mut values = Vec::new();
 
@@ -779,10 +867,20 @@ impl Parser {
         let name = Identifier::from(start);
         let type_token = self.require()?;
         let value_type = self.type_reference(type_token)?;
-        let location =
-            SourceLocation::start_end(&start_loc, value_type.location());
+        let default = if self.peek().kind == TokenKind::Assign {
+            self.next();
+
+            let token = self.require()?;
 
-        Ok(MethodArgument { name, value_type, location })
+            Some(self.expression(token)?)
+        } else {
+            None
+        };
+        let end_loc =
+            default.as_ref().map_or(value_type.location(), |n| n.location());
+        let location = SourceLocation::start_end(&start_loc, end_loc);
+
+        Ok(MethodArgument { name, value_type, default, location })
     }
 
     fn optional_closure_arguments(
@@ -3030,8 +3128,19 @@ impl Parser {
 
     fn next(&mut self) -> Token {
         loop {
-            let token =
-                self.peeked.take().unwrap_or_else(|| self.lexer.next_token());
+            // Peeked tokens are produced by this method, meaning any pragma
+            // they contain is already recorded.
+            let token = if let Some(token) = self.peeked.take() {
+                token
+            } else {
+                let token = self.lexer.next_token();
+
+                if token.kind == TokenKind::Comment {
+                    self.pragma(&token);
+                }
+
+                token
+            };
 
             match token.kind {
                 TokenKind::Comment if self.comments => return token,
@@ -3041,6 +3150,35 @@ impl Parser {
         }
     }
 
+    /// Records the warnings allowed by a comment in the form of
+    /// `# inko: allow(NAME, ...)`.
+    ///
+    /// Such comments are only recognized at the start of a line, such that
+    /// they can't be (accidentally) used inside methods and the like.
+    fn pragma(&mut self, token: &Token) {
+        if *token.location.columns.start() != 1 {
+            return;
+        }
+
+        let names = token
+            .value
+            .strip_prefix("inko:")
+            .map(|v| v.trim())
+            .and_then(|v| v.strip_prefix("allow("))
+            .and_then(|v| v.trim_end().strip_suffix(')'));
+
+        if let Some(names) = names {
+            for name in names.split(',').map(|v| v.trim()) {
+                if !name.is_empty() {
+                    self.allowed_warnings.push(Identifier {
+                        name: name.to_string(),
+                        location: token.location.clone(),
+                    });
+                }
+            }
+        }
+    }
+
     fn peek(&mut self) -> &Token {
         if self.peeked.is_none() {
             self.peeked = Some(self.next());
@@ -3264,7 +3402,8 @@ mod tests {
             Module {
                 expressions: Vec::new(),
                 file: PathBuf::from("test.inko"),
-                location: cols(1, 1)
+                location: cols(1, 1),
+                allowed_warnings: Vec::new(),
             }
         );
 
@@ -3273,7 +3412,8 @@ mod tests {
             Module {
                 expressions: Vec::new(),
                 file: PathBuf::from("test.inko"),
-                location: cols(1, 2)
+                location: cols(1, 2),
+                allowed_warnings: Vec::new(),
             }
         );
 
@@ -3282,11 +3422,84 @@ mod tests {
             Module {
                 expressions: Vec::new(),
                 file: PathBuf::from("test.inko"),
-                location: location(1..=2, 1..=2)
+                location: location(1..=2, 1..=2),
+                allowed_warnings: Vec::new(),
             }
         );
     }
 
+    #[test]
+    fn test_allowed_warnings() {
+        let module = parse(
+            "# inko: allow(unused-variable, unreachable)
+#inko:allow( deprecated )
+# inko: allow()
+# allow(foo)
+fn a {
+  # inko: allow(bar)
+}",
+        );
+        let names: Vec<_> =
+            module.allowed_warnings.iter().map(|v| v.name.as_str()).collect();
+
+        assert_eq!(names, vec!["unused-variable", "unreachable", "deprecated"]);
+        assert_eq!(module.allowed_warnings[0].location, cols(1, 43));
+    }
+
+    #[test]
+    fn test_allowed_warnings_with_comments() {
+        let module = parse_with_comments(
+            "import foo\n# inko: allow(unused-variable)\nlet A = 10\n# inko: allow(deprecated)\nfn a {}",
+        );
+        let names: Vec<_> =
+            module.allowed_warnings.iter().map(|v| v.name.as_str()).collect();
+
+        assert_eq!(names, vec!["unused-variable", "deprecated"]);
+    }
+
+    #[test]
+    fn test_parse_with_recovery() {
+        let mut parser = parser("fn a {\n  let\n}\nfn b -> {\n}\nfn c {}\n");
+        let (module, errors) = parser.parse_with_recovery();
+        let lines: Vec<_> =
+            errors.iter().map(|e| *e.location.lines.start()).collect();
+
+        assert_eq!(lines, vec![3, 4]);
+        assert_eq!(module.expressions.len(), 1);
+        assert!(matches!(
+            &module.expressions[0],
+            TopLevelExpression::DefineMethod(n) if n.name.name == "c"
+        ));
+    }
+
+    #[test]
+    fn test_parse_returns_the_first_error() {
+        let error = parser("fn a -> {}\nfn b -> {}").parse().unwrap_err();
+
+        assert_eq!(*error.location.lines.start(), 1);
+    }
+
+    #[test]
+    fn test_public_imports() {
+        assert_eq!(
+            top(parse("import pub foo")),
+            TopLevelExpression::Import(Box::new(Import {
+                path: ImportPath {
+                    steps: vec![Identifier {
+                        name: "foo".to_string(),
+                        location: cols(12, 14)
+                    }],
+                    location: cols(12, 14)
+                },
+                symbols: None,
+                tags: None,
+                include: true,
+                public: true,
+                location: cols(1, 14)
+            }))
+        );
+    }
+
     #[test]
     fn test_imports() {
         assert_eq!(
@@ -3302,6 +3515,7 @@ mod tests {
                 symbols: None,
                 tags: None,
                 include: true,
+                public: false,
                 location: cols(1, 10)
             }))
         );
@@ -3319,6 +3533,7 @@ mod tests {
                 symbols: None,
                 tags: None,
                 include: true,
+                public: false,
                 location: cols(1, 10)
             }))
         );
@@ -3336,6 +3551,7 @@ mod tests {
                 symbols: None,
                 tags: None,
                 include: true,
+                public: false,
                 location: cols(1, 10)
             }))
         );
@@ -3359,6 +3575,7 @@ mod tests {
                 symbols: None,
                 tags: None,
                 include: true,
+                public: false,
                 location: cols(1, 14)
             }))
         );
@@ -3391,6 +3608,7 @@ mod tests {
                     location: cols(12, 25)
                 }),
                 include: true,
+                public: false,
                 location: cols(1, 25)
             }))
         );
@@ -3448,6 +3666,7 @@ mod tests {
                 }),
                 tags: None,
                 include: true,
+                public: false,
                 location: cols(1, 17)
             }))
         );
@@ -3472,6 +3691,7 @@ mod tests {
                 }),
                 tags: None,
                 include: true,
+                public: false,
                 location: cols(1, 16)
             }))
         );
@@ -3503,6 +3723,7 @@ mod tests {
                 }),
                 tags: None,
                 include: true,
+                public: false,
                 location: cols(1, 21)
             }))
         );
@@ -3534,6 +3755,7 @@ mod tests {
                 }),
                 tags: None,
                 include: true,
+                public: false,
                 location: cols(1, 22)
             }))
         );
@@ -3561,6 +3783,7 @@ mod tests {
                 }),
                 tags: None,
                 include: true,
+                public: false,
                 location: cols(1, 17)
             }))
         );
@@ -3591,6 +3814,7 @@ mod tests {
                 }),
                 tags: None,
                 include: true,
+                public: false,
                 location: cols(1, 23)
             }))
         );
@@ -3618,6 +3842,7 @@ mod tests {
                 }),
                 tags: None,
                 include: true,
+                public: false,
                 location: cols(1, 23)
             }))
         );
@@ -3645,6 +3870,7 @@ mod tests {
                 }),
                 tags: None,
                 include: true,
+                public: false,
                 location: cols(1, 24)
             }))
         );
@@ -3672,6 +3898,7 @@ mod tests {
                 }),
                 tags: None,
                 include: true,
+                public: false,
                 location: cols(1, 22)
             }))
         );
@@ -3823,6 +4050,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_constant_with_call() {
+        assert_eq!(
+            top(parse("let A = embed_string('a.txt')")),
+            TopLevelExpression::DefineConstant(Box::new(DefineConstant {
+                public: false,
+                name: Constant {
+                    source: None,
+                    name: "A".to_string(),
+                    location: cols(5, 5)
+                },
+                value: Expression::Call(Box::new(Call {
+                    receiver: None,
+                    name: Identifier {
+                        name: "embed_string".to_string(),
+                        location: cols(9, 20)
+                    },
+                    arguments: Some(Arguments {
+                        values: vec![Argument::Positional(Expression::String(
+                            Box::new(StringLiteral {
+                                values: vec![StringValue::Text(Box::new(
+                                    StringText {
+                                        value: "a.txt".to_string(),
+                                        location: cols(23, 27)
+                                    }
+                                ))],
+                                location: cols(22, 28)
+                            })
+                        ))],
+                        location: cols(21, 29)
+                    }),
+                    location: cols(9, 29)
+                })),
+                location: cols(1, 29)
+            }))
+        );
+    }
+
     #[test]
     fn test_invalid_constants() {
         assert_error!("let A = B.new", cols(10, 10));
@@ -4471,6 +4736,7 @@ mod tests {
                                 arguments: None,
                                 location: cols(12, 12)
                             })),
+                            default: None,
                             location: cols(9, 12),
                         },
                         MethodArgument {
@@ -4487,6 +4753,7 @@ mod tests {
                                 arguments: None,
                                 location: cols(18, 18)
                             })),
+                            default: None,
                             location: cols(15, 18),
                         }
                     ],
@@ -4503,6 +4770,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_methods_with_default_arguments() {
+        assert_eq!(
+            top(parse("fn foo (a: A = 10) {}")),
+            TopLevelExpression::DefineMethod(Box::new(DefineMethod {
+                public: false,
+                operator: false,
+                kind: MethodKind::Instance,
+                name: Identifier {
+                    name: "foo".to_string(),
+                    location: cols(4, 6)
+                },
+                type_parameters: None,
+                arguments: Some(MethodArguments {
+                    values: vec![MethodArgument {
+                        name: Identifier {
+                            name: "a".to_string(),
+                            location: cols(9, 9)
+                        },
+                        value_type: Type::Named(Box::new(TypeName {
+                            name: Constant {
+                                source: None,
+                                name: "A".to_string(),
+                                location: cols(12, 12),
+                            },
+                            arguments: None,
+                            location: cols(12, 12)
+                        })),
+                        default: Some(Expression::Int(Box::new(IntLiteral {
+                            value: "10".to_string(),
+                            location: cols(16, 17)
+                        }))),
+                        location: cols(9, 17),
+                    }],
+                    variadic: false,
+                    location: cols(8, 18)
+                }),
+                return_type: None,
+                body: Some(Expressions {
+                    values: Vec::new(),
+                    location: cols(20, 21)
+                }),
+                location: cols(1, 21)
+            }))
+        );
+    }
+
     #[test]
     fn test_method_with_return_type() {
         assert_eq!(
@@ -5977,6 +6291,7 @@ mod tests {
                                             location: cols(22, 22)
                                         }
                                     )),
+                                    default: None,
                                     location: cols(19, 22)
                                 }],
                                 variadic: false,
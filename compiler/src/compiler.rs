@@ -1,13 +1,16 @@
-use crate::config::{BuildDirectories, Output};
+use crate::config::{BuildDirectories, Output, BUILD_LOCK};
 use crate::config::{Config, SOURCE, SOURCE_EXT, TESTS};
 use crate::hir;
 use crate::linker::link;
 use crate::llvm;
+use crate::lock::Lock;
 use crate::mir::passes as mir;
 use crate::mir::printer::to_dot;
 use crate::mir::specialize::Specialize;
 use crate::mir::Mir;
 use crate::modules_parser::{ModulesParser, ParsedModule};
+use crate::pkg::manifest::{Manifest, MANIFEST_FILE};
+use crate::pkg::version::Version;
 use crate::state::State;
 use crate::type_check::define_types::{
     CheckTraitImplementations, CheckTraitRequirements, CheckTypeParameters,
@@ -21,6 +24,8 @@ use crate::type_check::methods::{
     CheckMainMethod, DefineMethods, DefineModuleMethodNames,
     ImplementTraitMethods,
 };
+use crate::type_check::recursion::CheckRecursion;
+use std::collections::HashSet;
 use std::env::current_dir;
 use std::ffi::OsStr;
 use std::fs::write;
@@ -92,6 +97,42 @@ pub(crate) fn all_source_modules(
     Ok(modules)
 }
 
+/// Checks if the project and its installed dependencies support the version of
+/// Inko that's being used.
+///
+/// This check is performed before parsing any source code, such that using
+/// syntax or APIs introduced in a newer version of Inko results in a clear
+/// error, instead of a parsing or type error that makes no sense.
+fn check_inko_versions(config: &Config) -> Result<(), String> {
+    let current = Version::inko();
+    let root = config.source.parent().unwrap_or(&config.source);
+    let mut manifests = vec![("This project".to_string(), root.to_path_buf())];
+    let mut checked = HashSet::new();
+
+    while let Some((name, dir)) = manifests.pop() {
+        let path = dir.join(MANIFEST_FILE);
+
+        if !checked.insert(path.clone()) || !path.is_file() {
+            continue;
+        }
+
+        let manifest = Manifest::load(&path)?;
+
+        manifest.check_inko_version(&name, &current)?;
+
+        for dep in manifest.into_dependencies() {
+            let dir = config
+                .dependencies
+                .join(dep.url.directory_name())
+                .join(format!("v{}", dep.version.major));
+
+            manifests.push((dep.url.to_string(), dir));
+        }
+    }
+
+    Ok(())
+}
+
 fn format_timing(duration: Duration, total: Option<Duration>) -> String {
     let base = if duration.as_secs() >= 1 {
         format!("{:.2} sec", duration.as_secs_f64())
@@ -162,16 +203,26 @@ pub enum CompileError {
 pub struct Compiler {
     state: State,
     timings: Timings,
+
+    /// The lock on the build directory, if acquired.
+    ///
+    /// The lock is held until the compiler is dropped, such that commands
+    /// running the executable (e.g. `inko run`) keep holding it while the
+    /// executable runs.
+    lock: Option<Lock>,
 }
 
 impl Compiler {
     pub fn new(config: Config) -> Self {
-        Self { state: State::new(config), timings: Timings::new() }
+        Self { state: State::new(config), timings: Timings::new(), lock: None }
     }
 
     pub fn check(&mut self, file: Option<PathBuf>) -> Result<(), CompileError> {
         let start = Instant::now();
 
+        check_inko_versions(&self.state.config)
+            .map_err(CompileError::Internal)?;
+
         // When checking a project we want to fall back to checking _all_ files
         // including tests, not just the main module.
         //
@@ -199,6 +250,10 @@ impl Compiler {
         file: Option<PathBuf>,
     ) -> Result<PathBuf, CompileError> {
         let start = Instant::now();
+
+        check_inko_versions(&self.state.config)
+            .map_err(CompileError::Internal)?;
+
         let file = self.main_module_path(file)?;
         let main_mod = self.state.db.main_module().unwrap().clone();
         let ast = self.parse(vec![(main_mod, file.clone())]);
@@ -210,6 +265,7 @@ impl Compiler {
         let dirs = BuildDirectories::new(&self.state.config);
 
         dirs.create().map_err(CompileError::Internal)?;
+        self.lock_build_directory().map_err(CompileError::Internal)?;
 
         if self.state.config.dot {
             self.write_dot(&dirs, &mir)?;
@@ -225,6 +281,20 @@ impl Compiler {
         self.state.config.presenter.present(&self.state.diagnostics);
     }
 
+    /// Returns the warnings produced so far, formatted as a baseline for use
+    /// with `apply_baseline()`.
+    ///
+    /// Paths are made relative to `root`.
+    pub fn warnings_baseline(&self, root: &Path) -> String {
+        self.state.diagnostics.baseline(root)
+    }
+
+    /// Removes the warnings present in `baseline` from the diagnostics to
+    /// present, returning `true` if any new warnings remain.
+    pub fn apply_baseline(&mut self, root: &Path, baseline: &str) -> bool {
+        self.state.diagnostics.apply_baseline(root, baseline)
+    }
+
     pub fn print_timings(&self) {
         let total = self.timings.total;
 
@@ -290,6 +360,21 @@ LLVM module timings:
         BuildDirectories::new(&self.state.config).create_build()
     }
 
+    /// Acquires a lock on the build directory, if not done so already.
+    ///
+    /// Concurrent builds using the same build directory would otherwise
+    /// overwrite each other's object files and executables. The lock is
+    /// released when the compiler is dropped.
+    pub fn lock_build_directory(&mut self) -> Result<(), String> {
+        if self.lock.is_none() {
+            let dirs = BuildDirectories::new(&self.state.config);
+
+            self.lock = Some(Lock::acquire(&dirs.build.join(BUILD_LOCK))?);
+        }
+
+        Ok(())
+    }
+
     fn main_module_path(
         &mut self,
         file: Option<PathBuf>,
@@ -349,6 +434,7 @@ LLVM module timings:
         self.timings.mir = start.elapsed();
 
         if ok {
+            self.state.diagnostics.unused_allowed_warnings();
             Ok(mir)
         } else {
             Err(CompileError::Invalid)
@@ -408,6 +494,10 @@ LLVM module timings:
             && DefineConstants::run_all(state, modules)
             && Expressions::run_all(state, modules);
 
+        if res && state.config.lint_recursion {
+            CheckRecursion::run_all(state, modules);
+        }
+
         self.timings.type_check = start.elapsed();
         res
     }
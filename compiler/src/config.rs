@@ -30,6 +30,9 @@ const MAIN_TEST_MODULE: &str = "inko-tests";
 /// The name of the directory to store build files in.
 const BUILD: &str = "build";
 
+/// The name of the file used to lock a build directory.
+pub(crate) const BUILD_LOCK: &str = "build.lock";
+
 fn home_dir() -> Option<PathBuf> {
     env::var_os("HOME").filter(|v| !v.is_empty()).map(PathBuf::from)
 }
@@ -131,6 +134,10 @@ pub enum Opt {
     /// An aggressive number of optimisations is applied, favouring runtime
     /// performance over compile times.
     Aggressive,
+
+    /// Optimisations are applied that favour reducing the size of the
+    /// executable over runtime performance.
+    Size,
 }
 
 impl Opt {
@@ -139,6 +146,7 @@ impl Opt {
             Opt::None => Some("none"),
             Opt::Balanced => None,
             Opt::Aggressive => Some("aggressive"),
+            Opt::Size => Some("size"),
         }
     }
 }
@@ -248,6 +256,10 @@ pub struct Config {
     /// If C libraries should be linked statically or not.
     pub static_linking: bool,
 
+    /// If symbols and debug information should be stripped from the
+    /// executable.
+    pub strip: bool,
+
     /// The number of threads to use when performing work in parallel.
     pub threads: usize,
 
@@ -270,6 +282,12 @@ pub struct Config {
 
     /// Custom constant values to set at compile time.
     pub compile_time_variables: HashMap<(ModuleName, String), String>,
+
+    /// If potentially unbounded recursion should produce warnings.
+    pub lint_recursion: bool,
+
+    /// If assertions should be enabled regardless of the optimisation level.
+    pub keep_assertions: bool,
 }
 
 impl Config {
@@ -298,12 +316,15 @@ impl Config {
             verify_llvm: false,
             write_llvm: false,
             static_linking: false,
+            strip: false,
             threads: available_parallelism().map(|v| v.get()).unwrap_or(1),
             linker: Linker::Detect,
             linker_arguments: Vec::new(),
             incremental: true,
             compiled_at,
             compile_time_variables: HashMap::new(),
+            lint_recursion: false,
+            keep_assertions: false,
         }
     }
 
@@ -337,11 +358,20 @@ impl Config {
         }
     }
 
+    /// Returns `true` if assertions (using `std.debug.assert`) are enabled.
+    ///
+    /// Assertions are disabled when optimising for performance or size, unless
+    /// explicitly kept.
+    pub(crate) fn assertions(&self) -> bool {
+        self.keep_assertions || matches!(self.opt, Opt::None | Opt::Balanced)
+    }
+
     pub fn set_opt(&mut self, name: &str) -> Result<(), String> {
         self.opt = match name {
             "none" => Opt::None,
             "balanced" => Opt::Balanced,
             "aggressive" => Opt::Aggressive,
+            "size" => Opt::Size,
             _ => {
                 return Err(format!(
                     "The optimisation level '{}' isn't supported",
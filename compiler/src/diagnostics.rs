@@ -1,7 +1,9 @@
 //! Types and methods for producing compiler diagnostics.
 use ast::source_location::SourceLocation;
+use std::collections::HashMap;
 use std::fmt;
-use std::path::PathBuf;
+use std::mem::take;
+use std::path::{Path, PathBuf};
 
 /// The unique ID of a diagnostic.
 #[derive(PartialEq, Eq, Copy, Clone)]
@@ -29,6 +31,9 @@ pub(crate) enum DiagnosticId {
     Unreachable,
     UnusedVariable,
     Deprecated,
+    UnboundedRecursion,
+    UnusedAllow,
+    ConstantCondition,
 }
 
 impl fmt::Display for DiagnosticId {
@@ -56,13 +61,31 @@ impl fmt::Display for DiagnosticId {
             DiagnosticId::MissingMain => "missing-main",
             DiagnosticId::InvalidCast => "invalid-cast",
             DiagnosticId::UnusedVariable => "unused-variable",
+            DiagnosticId::UnboundedRecursion => "unbounded-recursion",
+            DiagnosticId::UnusedAllow => "unused-allow",
             DiagnosticId::Deprecated => "deprecated",
+            DiagnosticId::ConstantCondition => "constant-condition",
         };
 
         write!(f, "{}", id)
     }
 }
 
+impl DiagnosticId {
+    /// Returns the ID of a warning that can be silenced using an `allow`
+    /// comment.
+    pub(crate) fn allowed_warning(name: &str) -> Option<DiagnosticId> {
+        match name {
+            "unreachable" => Some(DiagnosticId::Unreachable),
+            "unused-variable" => Some(DiagnosticId::UnusedVariable),
+            "deprecated" => Some(DiagnosticId::Deprecated),
+            "unbounded-recursion" => Some(DiagnosticId::UnboundedRecursion),
+            "constant-condition" => Some(DiagnosticId::ConstantCondition),
+            _ => None,
+        }
+    }
+}
+
 impl fmt::Debug for DiagnosticId {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self)
@@ -85,6 +108,28 @@ impl fmt::Display for DiagnosticType {
     }
 }
 
+/// A secondary location attached to a diagnostic, such as the location of a
+/// related definition.
+pub(crate) struct Note {
+    message: String,
+    file: PathBuf,
+    location: SourceLocation,
+}
+
+impl Note {
+    pub(crate) fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub(crate) fn file(&self) -> &PathBuf {
+        &self.file
+    }
+
+    pub(crate) fn location(&self) -> &SourceLocation {
+        &self.location
+    }
+}
+
 /// A single diagnostic such as a warning or error.
 pub(crate) struct Diagnostic {
     kind: DiagnosticType,
@@ -92,6 +137,7 @@ pub(crate) struct Diagnostic {
     message: String,
     file: PathBuf,
     location: SourceLocation,
+    notes: Vec<Note>,
 }
 
 impl Diagnostic {
@@ -102,7 +148,7 @@ impl Diagnostic {
         file: PathBuf,
         location: SourceLocation,
     ) -> Self {
-        Self { kind, id, message, file, location }
+        Self { kind, id, message, file, location, notes: Vec::new() }
     }
 
     pub(crate) fn is_error(&self) -> bool {
@@ -128,12 +174,38 @@ impl Diagnostic {
     pub(crate) fn location(&self) -> &SourceLocation {
         &self.location
     }
+
+    pub(crate) fn notes(&self) -> &[Note] {
+        &self.notes
+    }
+
+    /// Returns `true` if `self` and `other` report the same problem in the
+    /// same file, though not necessarily at the same location.
+    pub(crate) fn same_problem(&self, other: &Diagnostic) -> bool {
+        self.id == other.id
+            && self.is_error() == other.is_error()
+            && self.message == other.message
+            && self.file == other.file
+    }
+}
+
+/// A warning silenced for a module.
+struct AllowedWarning {
+    id: DiagnosticId,
+    file: PathBuf,
+    location: SourceLocation,
+
+    /// A flag indicating one or more warnings have been silenced.
+    used: bool,
 }
 
 /// A collection of warnings and errors.
 pub(crate) struct Diagnostics {
     values: Vec<Diagnostic>,
 
+    /// The warnings silenced for specific modules.
+    allowed: Vec<AllowedWarning>,
+
     /// A flag indicating one or more errors have been produced.
     ///
     /// We use a dedicated flag as checking for the presence of errors happens
@@ -144,7 +216,7 @@ pub(crate) struct Diagnostics {
 
 impl Diagnostics {
     pub(crate) fn new() -> Self {
-        Self { values: Vec::new(), errors: false }
+        Self { values: Vec::new(), allowed: Vec::new(), errors: false }
     }
 
     pub(crate) fn has_errors(&self) -> bool {
@@ -158,6 +230,13 @@ impl Diagnostics {
         file: PathBuf,
         location: SourceLocation,
     ) {
+        if let Some(allowed) =
+            self.allowed.iter_mut().find(|a| a.id == id && a.file == file)
+        {
+            allowed.used = true;
+            return;
+        }
+
         self.values.push(Diagnostic::new(
             DiagnosticType::Warning,
             id,
@@ -185,6 +264,142 @@ impl Diagnostics {
         ));
     }
 
+    /// Silences all warnings with the given ID produced for `file`.
+    pub(crate) fn allow(
+        &mut self,
+        id: DiagnosticId,
+        file: PathBuf,
+        location: SourceLocation,
+    ) {
+        self.allowed.push(AllowedWarning { id, file, location, used: false });
+    }
+
+    /// Produces a warning for every silenced warning that isn't produced.
+    ///
+    /// This must be called after all other warnings are produced.
+    pub(crate) fn unused_allowed_warnings(&mut self) {
+        for allowed in take(&mut self.allowed) {
+            if allowed.used {
+                continue;
+            }
+
+            self.warn(
+                DiagnosticId::UnusedAllow,
+                format!(
+                    "the warning '{}' is allowed, but never produced",
+                    allowed.id
+                ),
+                allowed.file,
+                allowed.location,
+            );
+        }
+    }
+
+    /// Returns the warnings produced, formatted as a baseline.
+    ///
+    /// A baseline contains one warning per line, sorted so the output is
+    /// stable. File paths are made relative to `root`, and the locations of
+    /// warnings aren't included, ensuring the baseline remains valid when
+    /// code is moved around.
+    pub(crate) fn baseline(&self, root: &Path) -> String {
+        let mut lines: Vec<_> = self
+            .values
+            .iter()
+            .filter(|d| !d.is_error())
+            .map(|d| baseline_line(d, root))
+            .collect();
+
+        lines.sort();
+
+        let mut output = BASELINE_HEADER.to_string();
+
+        for line in lines {
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        output
+    }
+
+    /// Removes the warnings present in the given baseline, returning `true` if
+    /// any warnings remain.
+    ///
+    /// Each line in the baseline silences a single warning, so if a warning is
+    /// produced more often than it's listed in the baseline, the additional
+    /// warnings are kept.
+    pub(crate) fn apply_baseline(
+        &mut self,
+        root: &Path,
+        baseline: &str,
+    ) -> bool {
+        let mut known: HashMap<&str, usize> = HashMap::new();
+
+        for line in baseline.lines() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            *known.entry(line).or_insert(0) += 1;
+        }
+
+        self.values.retain(|diag| {
+            if diag.is_error() {
+                return true;
+            }
+
+            match known.get_mut(baseline_line(diag, root).as_str()) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    false
+                }
+                _ => true,
+            }
+        });
+
+        self.values.iter().any(|d| !d.is_error())
+    }
+
+    /// Adds a note to the last diagnostic produced.
+    pub(crate) fn note<S: Into<String>>(
+        &mut self,
+        message: S,
+        file: PathBuf,
+        location: SourceLocation,
+    ) {
+        if let Some(diag) = self.values.last_mut() {
+            diag.notes.push(Note { message: message.into(), file, location });
+        }
+    }
+
+    /// Adds a note to the last diagnostic suggesting the name most similar to
+    /// the given name, if there is one.
+    pub(crate) fn suggest_name<I: IntoIterator<Item = String>>(
+        &mut self,
+        name: &str,
+        candidates: I,
+        file: PathBuf,
+        location: SourceLocation,
+    ) {
+        if let Some(found) = similar_name(name, candidates) {
+            self.note(format!("did you mean '{}'?", found), file, location);
+        }
+    }
+
+    /// Adds a note to the last diagnostic suggesting an import that makes the
+    /// undefined symbol available.
+    pub(crate) fn suggest_import(
+        &mut self,
+        import: String,
+        file: PathBuf,
+        location: SourceLocation,
+    ) {
+        self.note(
+            format!("this symbol can be imported using 'import {}'", import),
+            file,
+            location,
+        );
+    }
+
     pub(crate) fn undefined_symbol(
         &mut self,
         name: &str,
@@ -942,3 +1157,175 @@ impl Diagnostics {
         self.values.iter()
     }
 }
+
+/// The first line of a baseline file.
+const BASELINE_HEADER: &str = "# Warnings ignored by 'inko check --baseline', \
+    one per line: FILE<TAB>ID<TAB>MESSAGE\n";
+
+/// Formats a warning as a single line in a baseline file.
+fn baseline_line(diagnostic: &Diagnostic, root: &Path) -> String {
+    let file = diagnostic.file.strip_prefix(root).unwrap_or(&diagnostic.file);
+    let message = diagnostic.message.replace(['\t', '\n'], " ");
+
+    format!("{}\t{}\t{}", file.display(), diagnostic.id, message)
+}
+
+/// Returns the Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ac) in a.chars().enumerate() {
+        let mut prev = row[0];
+
+        row[0] = i + 1;
+
+        for (j, &bc) in b.iter().enumerate() {
+            let cur = row[j + 1];
+
+            row[j + 1] =
+                if ac == bc { prev } else { 1 + prev.min(cur).min(row[j]) };
+
+            prev = cur;
+        }
+    }
+
+    row[b.len()]
+}
+
+/// Returns the candidate most similar to `name`, if any is similar enough.
+///
+/// Candidates are compared case-insensitively, and a candidate that only
+/// differs in casing is always preferred. Ties are resolved by picking the
+/// candidate that comes first, so callers should order the candidates from the
+/// innermost to the outermost scope.
+fn similar_name<I: IntoIterator<Item = String>>(
+    name: &str,
+    candidates: I,
+) -> Option<String> {
+    let lower = name.to_lowercase();
+    let max = (name.chars().count() / 3).max(1);
+    let mut best: Option<(usize, String)> = None;
+
+    for candidate in candidates {
+        if candidate == name {
+            continue;
+        }
+
+        let dist = edit_distance(&lower, &candidate.to_lowercase());
+
+        if dist > max || best.as_ref().map_or(false, |(best, _)| *best <= dist)
+        {
+            continue;
+        }
+
+        best = Some((dist, candidate));
+    }
+
+    best.map(|(_, name)| name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("foo", "foo"), 0);
+        assert_eq!(edit_distance("foo", ""), 3);
+        assert_eq!(edit_distance("foo", "fo"), 1);
+        assert_eq!(edit_distance("foo", "bar"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn test_similar_name() {
+        assert_eq!(
+            similar_name("foo_bar", names(&["foo_baz", "foo_ba"])),
+            Some("foo_baz".to_string())
+        );
+        assert_eq!(
+            similar_name("to_strin", names(&["to_string", "to_array"])),
+            Some("to_string".to_string())
+        );
+        assert_eq!(
+            similar_name("string", names(&["String"])),
+            Some("String".to_string())
+        );
+        assert_eq!(similar_name("foo", names(&["foo"])), None);
+        assert_eq!(similar_name("foo", names(&["bar", "quix"])), None);
+        assert_eq!(similar_name("a", names(&[])), None);
+    }
+
+    fn location(line: usize) -> SourceLocation {
+        SourceLocation::new(line..=line, 1..=1)
+    }
+
+    fn warnings() -> Diagnostics {
+        let mut diags = Diagnostics::new();
+        let file = PathBuf::from("/src/foo.inko");
+
+        diags.warn(
+            DiagnosticId::UnusedVariable,
+            "b",
+            file.clone(),
+            location(2),
+        );
+        diags.warn(
+            DiagnosticId::UnusedVariable,
+            "a",
+            file.clone(),
+            location(1),
+        );
+        diags.warn(
+            DiagnosticId::UnusedVariable,
+            "a",
+            file.clone(),
+            location(3),
+        );
+        diags.error(DiagnosticId::InvalidType, "c", file, location(4));
+        diags
+    }
+
+    #[test]
+    fn test_baseline() {
+        let diags = warnings();
+
+        assert_eq!(
+            diags.baseline(Path::new("/src")),
+            format!(
+                "{}foo.inko\tunused-variable\ta\n\
+                foo.inko\tunused-variable\ta\n\
+                foo.inko\tunused-variable\tb\n",
+                BASELINE_HEADER
+            )
+        );
+    }
+
+    #[test]
+    fn test_apply_baseline() {
+        let root = Path::new("/src");
+        let mut diags = warnings();
+        let baseline = diags.baseline(root);
+
+        assert!(!diags.apply_baseline(root, &baseline));
+        assert_eq!(diags.values.len(), 1);
+        assert!(diags.values[0].is_error());
+
+        let mut diags = warnings();
+
+        assert!(diags.apply_baseline(
+            root,
+            "# comment\n\nfoo.inko\tunused-variable\ta\n"
+        ));
+        assert_eq!(diags.values.len(), 3);
+        assert_eq!(diags.values[0].message, "b");
+        assert_eq!(diags.values[1].message, "a");
+        assert_eq!(diags.values[2].message, "c");
+    }
+}
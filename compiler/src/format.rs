@@ -353,6 +353,7 @@ impl Formatter {
 }
 
 struct Import<'a> {
+    public: bool,
     path: String,
     symbols: Option<Vec<(&'a String, Option<&'a String>)>>,
     tags: Option<Vec<&'a String>>,
@@ -554,7 +555,7 @@ impl Document {
                 .as_ref()
                 .map(|v| v.values.iter().map(|t| &t.name).collect::<Vec<_>>());
 
-            imports.push(Import { path, symbols, tags });
+            imports.push(Import { public: node.public, path, symbols, tags });
         }
 
         // Imports are sorted alphabetically based on their paths.
@@ -578,8 +579,8 @@ impl Document {
         let max = imports.len() - 1;
 
         for (idx, import) in imports.into_iter().enumerate() {
-            let mut nodes =
-                vec![Node::text("import "), Node::Text(import.path)];
+            let keyword = if import.public { "import pub " } else { "import " };
+            let mut nodes = vec![Node::text(keyword), Node::Text(import.path)];
             let syms_id = self.new_group_id();
 
             // Symbols are formatted in one of three ways:
@@ -1075,14 +1076,20 @@ impl Document {
             let mut args = nodes
                 .values
                 .iter()
-                .map(|n| (n.name.name.as_str(), Some(&n.value_type)))
+                .map(|n| {
+                    (
+                        n.name.name.as_str(),
+                        Some(&n.value_type),
+                        n.default.as_ref(),
+                    )
+                })
                 .collect::<Vec<_>>();
 
             if nodes.variadic {
-                args.push(("...", None));
+                args.push(("...", None, None));
             }
 
-            let vals = self.list(&args, header_id, |this, (name, typ)| {
+            let vals = self.list(&args, header_id, |this, (name, typ, def)| {
                 let mut pair = vec![Node::text(name)];
 
                 if let Some(typ) = typ {
@@ -1090,6 +1097,11 @@ impl Document {
                     pair.push(this.type_reference(typ));
                 }
 
+                if let Some(def) = def {
+                    pair.push(Node::text(" = "));
+                    pair.push(this.expression(def));
+                }
+
                 Node::Nodes(pair)
             });
 
@@ -8,7 +8,8 @@ use crate::modules_parser::ParsedModule;
 use crate::state::State;
 use ::ast::nodes::{self as ast, Node as _};
 use ::ast::source_location::SourceLocation;
-use std::path::PathBuf;
+use std::fs::read;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use types::{
     ARRAY_INTERNAL_NAME, ARRAY_LIMIT, ARRAY_PUSH, ARRAY_WITH_CAPACITY,
@@ -16,6 +17,8 @@ use types::{
 
 const BUILTIN_RECEIVER: &str = "_INKO";
 const ARRAY_LIT_VAR: &str = "$array";
+const EMBED_STRING: &str = "embed_string";
+const EMBED_BYTES: &str = "embed_bytes";
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) struct IntLiteral {
@@ -66,6 +69,14 @@ pub(crate) struct ConstStringLiteral {
     pub(crate) location: SourceLocation,
 }
 
+/// A constant `ByteArray`, produced by embedding the contents of a file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ConstBytesLiteral {
+    pub(crate) value: Vec<u8>,
+    pub(crate) resolved_type: types::TypeRef,
+    pub(crate) location: SourceLocation,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) struct TupleLiteral {
     pub(crate) class_id: Option<types::ClassId>,
@@ -177,6 +188,7 @@ pub(crate) struct ImportSymbol {
 pub(crate) struct Import {
     pub(crate) source: Vec<Identifier>,
     pub(crate) symbols: Vec<ImportSymbol>,
+    pub(crate) public: bool,
     pub(crate) location: SourceLocation,
 }
 
@@ -525,6 +537,7 @@ pub(crate) enum ConstExpression {
     Binary(Box<ConstBinary>),
     ConstantRef(Box<ConstantRef>),
     Array(Box<ConstArray>),
+    Bytes(Box<ConstBytesLiteral>),
     True(Box<True>),
     False(Box<False>),
 }
@@ -538,6 +551,7 @@ impl ConstExpression {
             Self::Binary(ref n) => &n.location,
             Self::ConstantRef(ref n) => &n.location,
             Self::Array(ref n) => &n.location,
+            Self::Bytes(ref n) => &n.location,
             Self::True(ref n) => &n.location,
             Self::False(ref n) => &n.location,
         }
@@ -549,6 +563,7 @@ impl ConstExpression {
             ConstExpression::Int(_)
                 | ConstExpression::Float(_)
                 | ConstExpression::String(_)
+                | ConstExpression::Bytes(_)
                 | ConstExpression::True(_)
                 | ConstExpression::False(_)
         )
@@ -568,6 +583,7 @@ pub(crate) struct TypeParameter {
 pub(crate) struct MethodArgument {
     pub(crate) name: Identifier,
     pub(crate) value_type: Type,
+    pub(crate) default: Option<Expression>,
     pub(crate) location: SourceLocation,
 }
 
@@ -600,6 +616,13 @@ impl Argument {
         }
     }
 
+    pub fn value(&self) -> &Expression {
+        match self {
+            Argument::Positional(n) => &n.value,
+            Argument::Named(n) => &n.value,
+        }
+    }
+
     pub fn into_value(self) -> Expression {
         match self {
             Argument::Positional(n) => n.value,
@@ -1165,7 +1188,7 @@ impl<'a> LowerToHir<'a> {
     }
 
     fn optional_method_arguments(
-        &self,
+        &mut self,
         node: Option<ast::MethodArguments>,
     ) -> Vec<MethodArgument> {
         if let Some(types) = node {
@@ -1175,10 +1198,11 @@ impl<'a> LowerToHir<'a> {
         }
     }
 
-    fn method_argument(&self, node: ast::MethodArgument) -> MethodArgument {
+    fn method_argument(&mut self, node: ast::MethodArgument) -> MethodArgument {
         MethodArgument {
             name: self.identifier(node.name),
             value_type: self.type_reference(node.value_type),
+            default: node.default.map(|n| self.expression(n)),
             location: node.location,
         }
     }
@@ -1534,6 +1558,7 @@ impl<'a> LowerToHir<'a> {
         TopLevelExpression::Import(Box::new(Import {
             source: self.import_module_path(node.path),
             symbols: self.import_symbols(node.symbols),
+            public: node.public,
             location: node.location,
         }))
     }
@@ -1765,10 +1790,121 @@ impl<'a> LowerToHir<'a> {
             ast::Expression::Array(node) => {
                 ConstExpression::Array(self.const_array(*node))
             }
+            ast::Expression::Call(node) => self.const_call(*node),
             _ => unreachable!(),
         }
     }
 
+    fn const_call(&mut self, node: ast::Call) -> ConstExpression {
+        let location = node.location;
+        let string = match node.name.name.as_str() {
+            EMBED_STRING => true,
+            EMBED_BYTES => false,
+            name => {
+                self.state.diagnostics.error(
+                    DiagnosticId::InvalidConstExpr,
+                    format!(
+                        "the function '{}' can't be used in a constant, \
+                        only '{}' and '{}' are supported",
+                        name, EMBED_STRING, EMBED_BYTES
+                    ),
+                    self.file(),
+                    location.clone(),
+                );
+
+                return self.const_string(String::new(), location);
+            }
+        };
+
+        let mut args = node.arguments.map(|n| n.values).unwrap_or_default();
+        let path = match (args.pop(), args.is_empty()) {
+            (
+                Some(ast::Argument::Positional(ast::Expression::String(arg))),
+                true,
+            ) => match self.const_string_literal(*arg) {
+                ConstExpression::String(lit) => lit.value,
+                _ => unreachable!(),
+            },
+            _ => {
+                self.state.diagnostics.error(
+                    DiagnosticId::InvalidConstExpr,
+                    format!(
+                        "'{}' expects a single String literal as its argument",
+                        node.name.name
+                    ),
+                    self.file(),
+                    location.clone(),
+                );
+
+                return self.const_string(String::new(), location);
+            }
+        };
+
+        // Paths are relative to the directory containing the module, such
+        // that the same path can be used regardless of where the compiler is
+        // run from.
+        let file = self.file();
+        let path = file.parent().unwrap_or(Path::new("")).join(path);
+        let bytes = match read(&path) {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                self.state.diagnostics.error(
+                    DiagnosticId::InvalidFile,
+                    format!(
+                        "the file '{}' can't be embedded: {}",
+                        path.display(),
+                        err
+                    ),
+                    file,
+                    location.clone(),
+                );
+
+                return self.const_string(String::new(), location);
+            }
+        };
+
+        self.module.add_embedded_file(&mut self.state.db, path.clone());
+
+        if !string {
+            return ConstExpression::Bytes(Box::new(ConstBytesLiteral {
+                value: bytes,
+                resolved_type: types::TypeRef::Unknown,
+                location,
+            }));
+        }
+
+        match String::from_utf8(bytes) {
+            Ok(value) => self.const_string(value, location),
+            Err(_) => {
+                self.state.diagnostics.error(
+                    DiagnosticId::InvalidConstExpr,
+                    format!(
+                        "the file '{}' can't be embedded as a String because \
+                        it doesn't contain valid UTF-8, use '{}' instead",
+                        path.display(),
+                        EMBED_BYTES
+                    ),
+                    file,
+                    location.clone(),
+                );
+
+                self.const_string(String::new(), location)
+            }
+        }
+    }
+
+    fn const_string(
+        &self,
+        value: String,
+        location: SourceLocation,
+    ) -> ConstExpression {
+        ConstExpression::String(Box::new(ConstStringLiteral {
+            value,
+            resolved_type: types::TypeRef::Unknown,
+            location,
+        }))
+    }
+
     fn int_literal(&mut self, node: ast::IntLiteral) -> IntLiteral {
         let mut input = node.value;
 
@@ -2013,11 +2149,7 @@ impl<'a> LowerToHir<'a> {
             }
         }
 
-        ConstExpression::String(Box::new(ConstStringLiteral {
-            value,
-            resolved_type: types::TypeRef::Unknown,
-            location: node.location,
-        }))
+        self.const_string(value, node.location)
     }
 
     fn const_binary(&mut self, node: ast::Binary) -> Box<ConstBinary> {
@@ -2663,6 +2795,12 @@ impl<'a> LowerToHir<'a> {
     }
 
     fn if_expression(&mut self, node: ast::If) -> Box<Match> {
+        self.check_condition(&node.if_true.condition);
+
+        for cond in &node.else_if {
+            self.check_condition(&cond.condition);
+        }
+
         let mut cases = vec![MatchCase {
             variable_ids: Vec::new(),
             pattern: Pattern::True(Box::new(True {
@@ -2733,6 +2871,25 @@ impl<'a> LowerToHir<'a> {
         })
     }
 
+    /// Produces a warning if a condition is always true or false.
+    ///
+    /// Only literal conditions are checked, as conditions using constants
+    /// (e.g. `if DEBUG { ... }`) are used to enable or disable code.
+    fn check_condition(&mut self, node: &ast::Expression) {
+        let value = match node {
+            ast::Expression::True(_) => "true",
+            ast::Expression::False(_) => "false",
+            _ => return,
+        };
+
+        self.state.diagnostics.warn(
+            DiagnosticId::ConstantCondition,
+            format!("this condition is always {}", value),
+            self.file(),
+            node.location().clone(),
+        );
+    }
+
     /// Desugars a `while` loop into a regular `loop`.
     ///
     /// Loops like this:
@@ -2751,6 +2908,8 @@ impl<'a> LowerToHir<'a> {
     ///       }
     ///     }
     fn while_expression(&mut self, node: ast::While) -> Box<Loop> {
+        self.check_condition(&node.condition);
+
         let location = node.condition.location().clone();
         let condition = self.expression(node.condition);
         let cond_body = self.expressions(node.body);
@@ -3234,6 +3393,68 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_lower_constant_with_embedded_string() {
+        let path = std::env::temp_dir().join("inko_hir_embed_string.txt");
+
+        std::fs::write(&path, "hello").unwrap();
+
+        let (hir, diags) = lower_top_expr(&format!(
+            "let A = embed_string('{}')",
+            path.display()
+        ));
+
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(diags, 0);
+
+        let TopLevelExpression::Constant(node) = hir else {
+            panic!("the top-level expression must be a constant")
+        };
+
+        let ConstExpression::String(lit) = node.value else {
+            panic!("the constant value must be a String")
+        };
+
+        assert_eq!(lit.value, "hello".to_string());
+    }
+
+    #[test]
+    fn test_lower_constant_with_embedded_bytes() {
+        let path = std::env::temp_dir().join("inko_hir_embed_bytes.bin");
+
+        std::fs::write(&path, [0, 255, 10]).unwrap();
+
+        let (hir, diags) = lower_top_expr(&format!(
+            "let A = embed_bytes('{}')",
+            path.display()
+        ));
+
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(diags, 0);
+
+        let TopLevelExpression::Constant(node) = hir else {
+            panic!("the top-level expression must be a constant")
+        };
+
+        let ConstExpression::Bytes(lit) = node.value else {
+            panic!("the constant value must be a ByteArray")
+        };
+
+        assert_eq!(lit.value, vec![0, 255, 10]);
+    }
+
+    #[test]
+    fn test_lower_constant_with_invalid_embed() {
+        assert_eq!(lower_top_expr("let A = embed_string('')").1, 1);
+        assert_eq!(lower_top_expr("let A = embed_string(10)").1, 1);
+        assert_eq!(lower_top_expr("let A = embed_string('a', 'b')").1, 1);
+        assert_eq!(lower_top_expr("let A = embed_images('a')").1, 1);
+        assert_eq!(
+            lower_top_expr("let A = embed_bytes('/this/does/not/exist')").1,
+            1
+        );
+    }
+
     #[test]
     fn test_lower_type_name() {
         let hir = lower_type("B[C]");
@@ -3450,6 +3671,7 @@ mod tests {
                         arguments: Vec::new(),
                         location: cols(17, 17)
                     })),
+                    default: None,
                     location: cols(14, 17)
                 }],
                 return_type: Some(Type::Named(Box::new(TypeName {
@@ -3527,6 +3749,7 @@ mod tests {
                         arguments: Vec::new(),
                         location: cols(18, 18)
                     })),
+                    default: None,
                     location: cols(15, 18)
                 }],
                 return_type: Some(Type::Named(Box::new(TypeName {
@@ -3845,6 +4068,7 @@ mod tests {
                                 arguments: Vec::new(),
                                 location: cols(29, 29)
                             })),
+                            default: None,
                             location: cols(26, 29)
                         }],
                         return_type: Some(Type::Named(Box::new(TypeName {
@@ -3917,6 +4141,7 @@ mod tests {
                                 arguments: Vec::new(),
                                 location: cols(28, 28)
                             })),
+                            default: None,
                             location: cols(25, 28)
                         }],
                         return_type: Some(Type::Named(Box::new(TypeName {
@@ -3988,6 +4213,7 @@ mod tests {
                                 arguments: Vec::new(),
                                 location: cols(22, 22)
                             })),
+                            default: None,
                             location: cols(19, 22)
                         }],
                         return_type: Some(Type::Named(Box::new(TypeName {
@@ -4130,6 +4356,7 @@ mod tests {
                                 arguments: Vec::new(),
                                 location: cols(22, 22)
                             })),
+                            default: None,
                             location: cols(19, 22)
                         }],
                         return_type: Some(Type::Named(Box::new(TypeName {
@@ -4261,6 +4488,7 @@ mod tests {
                                 arguments: Vec::new(),
                                 location: cols(22, 22)
                             })),
+                            default: None,
                             location: cols(19, 22)
                         }],
                         return_type: Some(Type::Named(Box::new(TypeName {
@@ -4675,6 +4903,7 @@ mod tests {
                     location: cols(8, 8)
                 }],
                 symbols: Vec::new(),
+                public: false,
                 location: cols(1, 8)
             }))
         );
@@ -4715,6 +4944,7 @@ mod tests {
                     },
                     location: cols(11, 11)
                 }],
+                public: false,
                 location: cols(1, 12)
             }))
         );
@@ -4742,6 +4972,7 @@ mod tests {
                     },
                     location: cols(11, 16)
                 }],
+                public: false,
                 location: cols(1, 17)
             }))
         );
@@ -4769,6 +5000,7 @@ mod tests {
                     },
                     location: cols(11, 14)
                 }],
+                public: false,
                 location: cols(1, 15)
             }))
         );
@@ -5980,6 +6212,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_lower_if_expression_with_literal_conditions() {
+        assert_eq!(lower_expr("fn a { if true { 10 } }").1, 1);
+        assert_eq!(lower_expr("fn a { if a { 10 } else if false {} }").1, 1);
+        assert_eq!(lower_expr("fn a { if a { 10 } }").1, 0);
+    }
+
+    #[test]
+    fn test_lower_while_expression_with_literal_conditions() {
+        assert_eq!(lower_expr("fn a { while true { 10 } }").1, 1);
+        assert_eq!(lower_expr("fn a { while false { 10 } }").1, 1);
+    }
+
     #[test]
     fn test_lower_while_expression() {
         let hir = lower_expr("fn a { while 10 { 20 } }").0;
@@ -7,6 +7,7 @@ mod hir;
 mod incremental;
 mod linker;
 mod llvm;
+pub mod lock;
 mod mir;
 mod modules_parser;
 pub mod pkg;
@@ -330,6 +330,16 @@ pub(crate) fn link(
         cmd.arg("-static");
     }
 
+    if state.config.strip {
+        // The linker used on macOS doesn't support -s, so we use the flags
+        // that strip debug information and local symbols instead.
+        if state.config.target.os.is_mac() {
+            cmd.arg("-Wl,-S,-x");
+        } else {
+            cmd.arg("-s");
+        }
+    }
+
     cmd.arg("-o");
     cmd.arg(output);
 
@@ -730,10 +730,10 @@ impl<'ctx> Builder<'ctx> {
         self.inner.build_unreachable().unwrap();
     }
 
-    pub(crate) fn string_bytes(&self, value: &str) -> ArrayValue<'ctx> {
+    pub(crate) fn bytes(&self, value: &[u8]) -> ArrayValue<'ctx> {
         let bytes = value
-            .bytes()
-            .map(|v| self.context.i8_type().const_int(v as _, false))
+            .iter()
+            .map(|&v| self.context.i8_type().const_int(v as _, false))
             .collect::<Vec<_>>();
 
         self.context.i8_type().const_array(&bytes)
@@ -40,7 +40,8 @@ use inkwell::values::{
 use inkwell::AddressSpace;
 use inkwell::OptimizationLevel;
 use std::collections::{HashMap, HashSet, VecDeque};
-use std::fs::{read, write};
+use std::fs::{read, remove_file, rename, write, File};
+use std::io;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -74,6 +75,14 @@ fn hash_compile_time_variables(state: &State) -> String {
     hasher.finalize().to_string()
 }
 
+fn ignore_not_found(error: io::Error) -> io::Result<()> {
+    if error.kind() == io::ErrorKind::NotFound {
+        Ok(())
+    } else {
+        Err(error)
+    }
+}
+
 fn check_object_cache(
     state: &mut State,
     symbol_names: &SymbolNames,
@@ -117,13 +126,6 @@ fn check_object_cache(
 
     if ver_changed {
         force = true;
-        write(&ver_path, new_ver).map_err(|e| {
-            format!(
-                "failed to write the compiler version to {}: {}",
-                ver_path.display(),
-                e
-            )
-        })?;
     }
 
     for (module, obj_path) in mir.modules.values().iter().zip(object_paths) {
@@ -134,8 +136,17 @@ fn check_object_cache(
         // We only check the timestamp if we aren't forced to flush the cache
         // already.
         if !changed {
-            let src_time =
-                src_path.metadata().and_then(|m| m.modified()).unwrap_or(now);
+            // Changes to files embedded into the module's constants require
+            // the module to be compiled again, just as changes to the module
+            // itself.
+            let src_time = module
+                .id
+                .embedded_files(&state.db)
+                .iter()
+                .chain(std::iter::once(&src_path))
+                .map(|p| p.metadata().and_then(|m| m.modified()).unwrap_or(now))
+                .max()
+                .unwrap_or(now);
 
             // We default to the Unix epoch such that a missing object file is
             // treated as one created in 1970. This way we don't need to wrap
@@ -229,6 +240,19 @@ fn check_object_cache(
             continue;
         }
 
+        // If the build is interrupted after we write the new hash but before
+        // the object file is written, the next build would consider the old
+        // object file up to date. To prevent that from happening we remove the
+        // object file first, such that a missing object file always results
+        // in it being compiled again.
+        remove_file(obj_path).or_else(ignore_not_found).map_err(|err| {
+            format!(
+                "failed to remove the object file {}: {}",
+                obj_path.display(),
+                err,
+            )
+        })?;
+
         // We only need to write to the hash file if there are any changes.
         write(&hash_path, new_hash).map_err(|err| {
             format!(
@@ -247,6 +271,18 @@ fn check_object_cache(
         }
     }
 
+    // The version is written last, such that an interrupted build still
+    // flushes the cache the next time.
+    if ver_changed {
+        write(&ver_path, new_ver).map_err(|e| {
+            format!(
+                "failed to write the compiler version to {}: {}",
+                ver_path.display(),
+                e
+            )
+        })?;
+    }
+
     Ok(())
 }
 
@@ -420,6 +456,10 @@ pub(crate) fn lower_all(
 
         // This is the equivalent of -O3 for clang.
         Opt::Aggressive => OptimizationLevel::Aggressive,
+
+        // This is the equivalent of -Oz for clang, which uses the default
+        // level for code generation.
+        Opt::Size => OptimizationLevel::Default,
     };
 
     // Our "queue" is just an atomic integer in the range 0..N where N is the
@@ -629,10 +669,10 @@ impl<'a> Worker<'a> {
     fn run_passes(&self, module: &Module, layouts: &Layouts) {
         let layout = layouts.target_data.get_data_layout();
         let opts = PassBuilderOptions::create();
-        let passes = if let Opt::Aggressive = self.shared.state.config.opt {
-            &["default<O3>"]
-        } else {
-            &["mem2reg"]
+        let passes = match self.shared.state.config.opt {
+            Opt::Aggressive => &["default<O3>"],
+            Opt::Size => &["default<Oz>"],
+            _ => &["mem2reg"],
         };
 
         module.set_data_layout(&layout);
@@ -669,8 +709,21 @@ impl<'a> Worker<'a> {
             })?;
         }
 
+        // LLVM writes object files in place, so an interrupted build may leave
+        // behind a partially written object file. To ensure we never reuse
+        // such files, we write to a temporary file first and move it into
+        // place once done.
+        let tmp_path = path.with_extension("o.tmp");
+
         self.machine
-            .write_to_file(&module.inner, FileType::Object, &path)
+            .write_to_file(&module.inner, FileType::Object, &tmp_path)
+            .map_err(|e| e.to_string())
+            .and_then(|_| {
+                File::open(&tmp_path)
+                    .and_then(|f| f.sync_all())
+                    .and_then(|_| rename(&tmp_path, &path))
+                    .map_err(|e| e.to_string())
+            })
             .map_err(|e| {
                 format!("failed to write object file {}: {}", path.display(), e)
             })
@@ -894,6 +947,15 @@ impl<'shared, 'module, 'ctx> LowerModule<'shared, 'module, 'ctx> {
                 builder.f64_literal(*val).as_basic_value_enum()
             }
             Constant::String(val) => self.new_string(builder, state, val),
+            Constant::Bytes(val) => {
+                let bytes = self.static_bytes(builder, val);
+                let len = builder.i64_literal(val.len() as _).into();
+                let func = self
+                    .module
+                    .runtime_function(RuntimeFunction::ByteArrayFromPointer);
+
+                builder.call(func, &[state.into(), bytes.into(), len])
+            }
             Constant::Bool(true) => {
                 builder.i64_literal(1).as_basic_value_enum()
             }
@@ -974,16 +1036,31 @@ impl<'shared, 'module, 'ctx> LowerModule<'shared, 'module, 'ctx> {
         state: PointerValue<'ctx>,
         value: &str,
     ) -> BasicValueEnum<'ctx> {
-        let bytes_typ = builder.context.i8_type().array_type(value.len() as _);
-        let bytes_var = builder.new_temporary(bytes_typ);
-        let bytes = builder.string_bytes(value);
-
-        builder.store(bytes_var, bytes);
-
+        let bytes = self.static_bytes(builder, value.as_bytes());
         let len = builder.u64_literal(value.len() as u64).into();
         let func = self.module.runtime_function(RuntimeFunction::StringNew);
 
-        builder.call(func, &[state.into(), bytes_var.into(), len])
+        builder.call(func, &[state.into(), bytes.into(), len])
+    }
+
+    /// Returns a pointer to a global containing the given bytes.
+    ///
+    /// Constants may contain the contents of (large) embedded files, so we
+    /// store the bytes in a global instead of on the stack.
+    fn static_bytes(
+        &self,
+        builder: &Builder<'ctx>,
+        value: &[u8],
+    ) -> PointerValue<'ctx> {
+        let typ = builder.context.i8_type().array_type(value.len() as _);
+        let global = self.module.add_global(typ, "");
+
+        // We use a private linkage so we don't need to generate a globally
+        // unique symbol name for the global.
+        global.set_linkage(Linkage::Private);
+        global.set_constant(true);
+        global.set_initializer(&builder.bytes(value));
+        global.as_pointer_value()
     }
 
     fn load_state(&mut self, builder: &Builder<'ctx>) -> PointerValue<'ctx> {
@@ -1551,6 +1628,74 @@ impl<'shared, 'module, 'ctx> LowerMethod<'shared, 'module, 'ctx> {
 
                         self.builder.store(reg_var, res);
                     }
+                    BuiltinFunction::IntCountOnes => {
+                        let reg_var = self.variables[&ins.register];
+                        let val_var = self.variables[&ins.arguments[0]];
+                        let val = self.builder.load_int(val_var).into();
+                        let func = self.module.intrinsic(
+                            "llvm.ctpop",
+                            &[self.builder.context.i64_type().into()],
+                        );
+                        let res =
+                            self.builder.call(func, &[val]).into_int_value();
+
+                        self.builder.store(reg_var, res);
+                    }
+                    BuiltinFunction::IntLeadingZeros => {
+                        let reg_var = self.variables[&ins.register];
+                        let val_var = self.variables[&ins.arguments[0]];
+                        let val = self.builder.load_int(val_var).into();
+                        let zero = self
+                            .builder
+                            .context
+                            .bool_type()
+                            .const_int(0, false)
+                            .into();
+                        let func = self.module.intrinsic(
+                            "llvm.ctlz",
+                            &[self.builder.context.i64_type().into()],
+                        );
+                        let res = self
+                            .builder
+                            .call(func, &[val, zero])
+                            .into_int_value();
+
+                        self.builder.store(reg_var, res);
+                    }
+                    BuiltinFunction::IntTrailingZeros => {
+                        let reg_var = self.variables[&ins.register];
+                        let val_var = self.variables[&ins.arguments[0]];
+                        let val = self.builder.load_int(val_var).into();
+                        let zero = self
+                            .builder
+                            .context
+                            .bool_type()
+                            .const_int(0, false)
+                            .into();
+                        let func = self.module.intrinsic(
+                            "llvm.cttz",
+                            &[self.builder.context.i64_type().into()],
+                        );
+                        let res = self
+                            .builder
+                            .call(func, &[val, zero])
+                            .into_int_value();
+
+                        self.builder.store(reg_var, res);
+                    }
+                    BuiltinFunction::IntSwapBytes => {
+                        let reg_var = self.variables[&ins.register];
+                        let val_var = self.variables[&ins.arguments[0]];
+                        let val = self.builder.load_int(val_var).into();
+                        let func = self.module.intrinsic(
+                            "llvm.bswap",
+                            &[self.builder.context.i64_type().into()],
+                        );
+                        let res =
+                            self.builder.call(func, &[val]).into_int_value();
+
+                        self.builder.store(reg_var, res);
+                    }
                     BuiltinFunction::IntShl => {
                         let reg_var = self.variables[&ins.register];
                         let lhs_var = self.variables[&ins.arguments[0]];
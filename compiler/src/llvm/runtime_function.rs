@@ -23,6 +23,7 @@ pub(crate) enum RuntimeFunction {
     Allocate,
     Free,
     AllocationError,
+    ByteArrayFromPointer,
 }
 
 impl RuntimeFunction {
@@ -51,6 +52,9 @@ impl RuntimeFunction {
             RuntimeFunction::Allocate => "malloc",
             RuntimeFunction::Free => "free",
             RuntimeFunction::AllocationError => "inko_alloc_error",
+            RuntimeFunction::ByteArrayFromPointer => {
+                "inko_byte_array_from_pointer"
+            }
         }
     }
 
@@ -188,6 +192,14 @@ impl RuntimeFunction {
 
                 ret.fn_type(&[ptr], false)
             }
+            RuntimeFunction::ByteArrayFromPointer => {
+                let state = module.layouts.state.ptr_type(space).into();
+                let bytes = context.pointer_type().into();
+                let length = context.i64_type().into();
+                let ret = context.pointer_type();
+
+                ret.fn_type(&[state, bytes, length], false)
+            }
         };
 
         module.add_function(self.name(), fn_type, None)
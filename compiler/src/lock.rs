@@ -0,0 +1,128 @@
+//! Locking of files shared between concurrent compiler invocations.
+//!
+//! Locks are acquired using `flock()`, meaning the operating system releases
+//! them when the process holding them terminates, even if it crashes. This
+//! ensures an interrupted build or package operation never leaves behind a
+//! lock that has to be removed manually.
+use std::env;
+use std::fs::{create_dir_all, read_to_string, File, OpenOptions};
+use std::io::{Error, ErrorKind, Write};
+use std::os::fd::AsRawFd;
+use std::path::Path;
+use std::process;
+
+fn flock(file: &File, operation: i32) -> Result<(), Error> {
+    loop {
+        if unsafe { libc::flock(file.as_raw_fd(), operation) } == 0 {
+            return Ok(());
+        }
+
+        let error = Error::last_os_error();
+
+        if error.kind() != ErrorKind::Interrupted {
+            return Err(error);
+        }
+    }
+}
+
+/// An exclusive lock on a file, released when the lock is dropped.
+///
+/// While the lock is held, the file contains the ID and command line of the
+/// process holding the lock. This is used to tell users that are waiting for
+/// the lock what they are waiting for.
+pub struct Lock {
+    file: File,
+}
+
+impl Lock {
+    /// Acquires an exclusive lock on the file at `path`, creating the file
+    /// (and its parent directories) if necessary.
+    ///
+    /// If the lock is held by another process, a message is written to STDERR
+    /// and this method blocks until the lock is released.
+    pub fn acquire(path: &Path) -> Result<Lock, String> {
+        let error =
+            |e: Error| format!("Failed to lock {}: {}", path.display(), e);
+
+        if let Some(dir) = path.parent() {
+            create_dir_all(dir).map_err(error)?;
+        }
+
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .open(path)
+            .map_err(error)?;
+
+        match flock(&file, libc::LOCK_EX | libc::LOCK_NB) {
+            Ok(_) => {}
+            Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                let holder = read_to_string(path).unwrap_or_default();
+                let holder = holder.trim();
+
+                if holder.is_empty() {
+                    eprintln!(
+                        "Waiting for the lock on {} to be released",
+                        path.display()
+                    );
+                } else {
+                    eprintln!(
+                        "Waiting for the lock on {} held by {}",
+                        path.display(),
+                        holder
+                    );
+                }
+
+                flock(&file, libc::LOCK_EX).map_err(error)?;
+            }
+            Err(e) => return Err(error(e)),
+        }
+
+        let mut lock = Lock { file };
+        let command = env::args().collect::<Vec<_>>().join(" ");
+
+        lock.file
+            .set_len(0)
+            .and_then(|_| {
+                write!(lock.file, "process {} ({})", process::id(), command)
+            })
+            .map_err(error)?;
+
+        Ok(lock)
+    }
+}
+
+impl Drop for Lock {
+    fn drop(&mut self) {
+        // The holder information is cleared such that it doesn't linger around
+        // after the lock is released. The lock itself is released when the file
+        // is closed.
+        let _ = self.file.set_len(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::remove_dir_all;
+
+    #[test]
+    fn test_lock_acquire() {
+        let dir = env::temp_dir().join("inko-test_lock_acquire");
+        let path = dir.join("nested").join("test.lock");
+
+        {
+            let _lock = Lock::acquire(&path).unwrap();
+            let holder = read_to_string(&path).unwrap();
+
+            assert!(holder.starts_with(&format!("process {}", process::id())));
+        }
+
+        assert_eq!(read_to_string(&path).unwrap(), "");
+
+        // The lock is released when dropped, so we can acquire it again.
+        assert!(Lock::acquire(&path).is_ok());
+        remove_dir_all(dir).unwrap();
+    }
+}
@@ -681,6 +681,7 @@ pub(crate) enum Constant {
     Float(f64),
     String(String),
     Array(Vec<Constant>),
+    Bytes(Vec<u8>),
     Bool(bool),
 }
 
@@ -700,6 +701,7 @@ impl PartialEq for Constant {
             }
             (Constant::String(a), Constant::String(b)) => a == b,
             (Constant::Array(a), Constant::Array(b)) => a == b,
+            (Constant::Bytes(a), Constant::Bytes(b)) => a == b,
             _ => false,
         }
     }
@@ -714,6 +716,7 @@ impl Hash for Constant {
             Constant::Float(v) => v.to_bits().hash(state),
             Constant::String(v) => v.hash(state),
             Constant::Array(v) => v.hash(state),
+            Constant::Bytes(v) => v.hash(state),
             Constant::Bool(v) => v.hash(state),
         }
     }
@@ -726,6 +729,7 @@ impl fmt::Display for Constant {
             Self::Float(v) => write!(f, "{}", v),
             Self::String(v) => write!(f, "{:?}", v),
             Self::Array(v) => write!(f, "{:?}", v),
+            Self::Bytes(v) => write!(f, "{:?}", v),
             Self::Bool(v) => write!(f, "{}", v),
         }
     }
@@ -68,23 +68,24 @@ pub(crate) fn check_global_limits(state: &mut State) -> Result<(), String> {
 }
 
 pub(crate) fn define_default_compile_time_variables(state: &mut State) {
-    // "std.env" isn't imported by default, so only define the variables if this
-    // is actually possible.
-    if state.db.optional_module("std.env").is_none() {
-        return;
-    }
-
     let vars = [
-        ("std.env", "ARCH", state.config.target.arch_name()),
-        ("std.env", "OS", state.config.target.os_name()),
-        ("std.env", "ABI", state.config.target.abi_name()),
+        ("std.env", "ARCH", state.config.target.arch_name().to_string()),
+        ("std.env", "OS", state.config.target.os_name().to_string()),
+        ("std.env", "ABI", state.config.target.abi_name().to_string()),
+        ("std.debug", "ASSERTIONS", state.config.assertions().to_string()),
     ];
 
     for (module, name, val) in vars {
-        state.config.compile_time_variables.insert(
-            (ModuleName::new(module), name.to_string()),
-            val.to_string(),
-        );
+        // Modules such as "std.env" aren't imported by default, so only define
+        // the variables if this is actually possible.
+        if state.db.optional_module(module).is_none() {
+            continue;
+        }
+
+        state
+            .config
+            .compile_time_variables
+            .insert((ModuleName::new(module), name.to_string()), val);
     }
 }
 
@@ -701,6 +702,13 @@ pub(crate) struct DefineConstants<'a> {
     state: &'a mut State,
     mir: &'a mut Mir,
     module_id: types::ModuleId,
+
+    /// The definitions of all constants, along with the modules they are
+    /// defined in.
+    nodes: &'a HashMap<
+        types::ConstantId,
+        (types::ModuleId, &'a hir::DefineConstant),
+    >,
 }
 
 impl<'a> DefineConstants<'a> {
@@ -709,54 +717,57 @@ impl<'a> DefineConstants<'a> {
         mir: &mut Mir,
         modules: &Vec<hir::Module>,
     ) -> bool {
-        // Literal constants are defined first, as binary constants may depend
-        // on their values.
-        for module in modules {
-            let module_id = module.module_id;
+        let mut nodes = HashMap::new();
 
-            DefineConstants { state, mir, module_id }.define_literal(module);
+        for module in modules {
+            for expr in &module.expressions {
+                if let hir::TopLevelExpression::Constant(n) = expr {
+                    nodes.insert(
+                        n.constant_id.unwrap(),
+                        (module.module_id, &**n),
+                    );
+                }
+            }
         }
 
+        // Constants may refer to other constants defined later in the same
+        // module or in a different module. Such constants are defined when
+        // they're first referred to, and the type checker ensures constants
+        // don't depend on themselves.
         for module in modules {
             let module_id = module.module_id;
 
-            DefineConstants { state, mir, module_id }.define_binary(module);
+            DefineConstants { state, mir, module_id, nodes: &nodes }
+                .define(module);
         }
 
         !state.diagnostics.has_errors()
     }
 
-    /// Defines constants who's values are literals.
-    fn define_literal(&mut self, module: &hir::Module) {
+    fn define(&mut self, module: &hir::Module) {
         for expr in &module.expressions {
             if let hir::TopLevelExpression::Constant(n) = expr {
-                let id = n.constant_id.unwrap();
-                let val = match n.value {
-                    hir::ConstExpression::Int(ref n) => Constant::Int(n.value),
-                    hir::ConstExpression::String(ref n) => {
-                        Constant::String(n.value.clone())
-                    }
-                    hir::ConstExpression::Float(ref n) => {
-                        Constant::Float(n.value)
-                    }
-                    _ => continue,
-                };
-
-                self.mir.constants.insert(id, val);
+                self.constant(n.constant_id.unwrap());
             }
         }
     }
 
-    /// Defines constants who's values are binary expressions.
-    fn define_binary(&mut self, module: &hir::Module) {
-        for expr in &module.expressions {
-            if let hir::TopLevelExpression::Constant(n) = expr {
-                let id = n.constant_id.unwrap();
-                let val = self.expression(&n.value);
+    fn constant(&mut self, id: types::ConstantId) -> Constant {
+        if let Some(val) = self.mir.constants.get(&id) {
+            return val.clone();
+        }
 
-                self.mir.constants.insert(id, val);
-            }
+        let (module_id, node) = self.nodes[&id];
+        let val = DefineConstants {
+            state: self.state,
+            mir: self.mir,
+            module_id,
+            nodes: self.nodes,
         }
+        .expression(&node.value);
+
+        self.mir.constants.insert(id, val.clone());
+        val
     }
 
     fn expression(&mut self, node: &hir::ConstExpression) -> Constant {
@@ -770,14 +781,15 @@ impl<'a> DefineConstants<'a> {
             hir::ConstExpression::True(_) => Constant::Bool(true),
             hir::ConstExpression::False(_) => Constant::Bool(false),
             hir::ConstExpression::ConstantRef(ref n) => match n.kind {
-                types::ConstantKind::Constant(id) => {
-                    self.mir.constants.get(&id).cloned().unwrap()
-                }
+                types::ConstantKind::Constant(id) => self.constant(id),
                 _ => unreachable!(),
             },
             hir::ConstExpression::Array(ref n) => Constant::Array(
                 n.values.iter().map(|n| self.expression(n)).collect(),
             ),
+            hir::ConstExpression::Bytes(ref n) => {
+                Constant::Bytes(n.value.clone())
+            }
         }
     }
 
@@ -856,10 +868,10 @@ impl<'a> DefineConstants<'a> {
                     Constant::String(String::new())
                 }
             }
-            Constant::Array(_) | Constant::Bool(_) => {
+            Constant::Array(_) | Constant::Bytes(_) | Constant::Bool(_) => {
                 self.state.diagnostics.error(
                     DiagnosticId::InvalidConstExpr,
-                    "constant Array and Bool values don't support \
+                    "constant Array, ByteArray and Bool values don't support \
                     binary operations",
                     self.file(),
                     node.location.clone(),
@@ -1810,26 +1822,18 @@ impl<'a> LowerMethod<'a> {
         let entered = self.enter_call_scope();
         let loc = self.add_location(node.name.location);
         let reg = match node.kind {
+            types::CallKind::Call(info) if self.disabled_assertion(info.id) => {
+                self.check_inferred(info.returns, &node.location);
+                self.skip_assertion(info, node.receiver, node.arguments, loc)
+            }
             types::CallKind::Call(info) => {
                 self.check_inferred(info.returns, &node.location);
-
-                let returns = info.returns;
-                let rec = if info.receiver.is_explicit() {
-                    node.receiver.map(|expr| self.expression(expr))
-                } else {
-                    None
-                };
-
-                let args =
-                    node.arguments.into_iter().map(Argument::Regular).collect();
-
-                let result = self.call_method(info, rec, args, loc);
-
-                if returns.is_never(self.db()) {
-                    self.add_current_block();
-                }
-
-                result
+                self.call_with_arguments(
+                    info,
+                    node.receiver,
+                    node.arguments,
+                    loc,
+                )
             }
             types::CallKind::GetField(info) => {
                 self.check_inferred(info.variable_type, &node.location);
@@ -2030,6 +2034,73 @@ impl<'a> LowerMethod<'a> {
         result
     }
 
+    fn call_with_arguments(
+        &mut self,
+        info: types::CallInfo,
+        receiver: Option<hir::Expression>,
+        arguments: Vec<hir::Argument>,
+        location: LocationId,
+    ) -> RegisterId {
+        let returns = info.returns;
+        let rec = if info.receiver.is_explicit() {
+            receiver.map(|expr| self.expression(expr))
+        } else {
+            None
+        };
+
+        let args = arguments.into_iter().map(Argument::Regular).collect();
+        let result = self.call_method(info, rec, args, location);
+
+        if returns.is_never(self.db()) {
+            self.add_current_block();
+        }
+
+        result
+    }
+
+    /// Returns `true` if `method` is `std.debug.assert` and assertions are
+    /// disabled.
+    fn disabled_assertion(&self, method: MethodId) -> bool {
+        !self.state.config.assertions()
+            && method.name(self.db()) == "assert"
+            && method.module(self.db()).name(self.db()).as_str() == "std.debug"
+    }
+
+    /// Lowers a call to `std.debug.assert` for when assertions are disabled.
+    ///
+    /// The call is lowered into a block that's never entered, such that
+    /// neither the call nor its arguments are evaluated, and LLVM removes the
+    /// code entirely. The arguments are still lowered (instead of being
+    /// discarded) such that moves and uses of variables are handled the same
+    /// way as for `if false { assert(...) }`.
+    fn skip_assertion(
+        &mut self,
+        info: types::CallInfo,
+        receiver: Option<hir::Expression>,
+        arguments: Vec<hir::Argument>,
+        location: LocationId,
+    ) -> RegisterId {
+        let cond = self.new_untracked_register(TypeRef::boolean());
+        let before_id = self.current_block;
+        let call_id = self.add_block();
+        let after_id = self.add_block();
+
+        self.current_block_mut().false_literal(cond, location);
+        self.current_block_mut().branch(cond, call_id, after_id, location);
+        self.add_edge(before_id, call_id);
+        self.add_edge(before_id, after_id);
+
+        self.current_block = call_id;
+
+        self.enter_scope();
+        self.call_with_arguments(info, receiver, arguments, location);
+        self.add_edge(self.current_block, after_id);
+        self.exit_scope();
+
+        self.current_block = after_id;
+        self.get_nil(location)
+    }
+
     fn call_arguments(
         &mut self,
         method: MethodId,
@@ -4465,6 +4536,40 @@ impl<'a> LowerMethod<'a> {
         id: ConstantId,
         location: LocationId,
     ) {
+        // Int and Bool constants are stored in the register directly, allowing
+        // LLVM to remove code that depends on their values, such as assertions
+        // guarded by `std.debug.ASSERTIONS`.
+        //
+        // Constants defined in other modules are only inlined if they're
+        // compile-time variables. The object cache doesn't track the values of
+        // constants a module uses, so inlining any other constant would result
+        // in stale object files when the constant's value changes. Changes to
+        // compile-time variables on the other hand flush the entire cache.
+        let inline = id.module(self.db()) == self.module || {
+            let key = (
+                id.module(self.db()).name(self.db()).clone(),
+                id.name(self.db()).clone(),
+            );
+
+            self.state.config.compile_time_variables.contains_key(&key)
+        };
+
+        match self.mir.constants.get(&id).filter(|_| inline) {
+            Some(&Constant::Int(val)) => {
+                self.current_block_mut().int_literal(register, val, location);
+                return;
+            }
+            Some(Constant::Bool(true)) => {
+                self.current_block_mut().true_literal(register, location);
+                return;
+            }
+            Some(Constant::Bool(false)) => {
+                self.current_block_mut().false_literal(register, location);
+                return;
+            }
+            _ => {}
+        }
+
         self.current_block_mut().get_constant(register, id, location);
 
         // We don't need to handle Array here as it's exposed through a
@@ -6,7 +6,7 @@ use ast::parser::Parser;
 use ast::source_location::SourceLocation;
 use std::collections::{HashMap, HashSet};
 use std::fs::read;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use types::module_name::ModuleName;
 
 fn imported_modules(
@@ -158,17 +158,43 @@ impl<'a> ModulesParser<'a> {
 
         let mut parser = Parser::new(input, file.clone());
 
-        match parser.parse() {
-            Ok(ast) => Some(ast),
-            Err(err) => {
-                self.state.diagnostics.error(
-                    DiagnosticId::InvalidSyntax,
-                    err.message,
-                    file.clone(),
-                    err.location,
-                );
+        let (ast, errors) = parser.parse_with_recovery();
 
-                None
+        if errors.is_empty() {
+            self.allow_warnings(&ast, file);
+            return Some(ast);
+        }
+
+        for err in errors {
+            self.state.diagnostics.error(
+                DiagnosticId::InvalidSyntax,
+                err.message,
+                file.clone(),
+                err.location,
+            );
+        }
+
+        None
+    }
+
+    fn allow_warnings(&mut self, ast: &Module, file: &Path) {
+        for name in &ast.allowed_warnings {
+            if let Some(id) = DiagnosticId::allowed_warning(&name.name) {
+                self.state.diagnostics.allow(
+                    id,
+                    file.to_path_buf(),
+                    name.location.clone(),
+                );
+            } else {
+                self.state.diagnostics.warn(
+                    DiagnosticId::UnusedAllow,
+                    format!(
+                        "the warning '{}' doesn't exist or can't be allowed",
+                        name.name
+                    ),
+                    file.to_path_buf(),
+                    name.location.clone(),
+                );
             }
         }
     }
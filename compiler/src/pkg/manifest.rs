@@ -1,7 +1,7 @@
 use crate::pkg::version::Version;
 use blake3;
 use std::fmt;
-use std::fs::{File, OpenOptions};
+use std::fs::{rename, File, OpenOptions};
 use std::io::{BufRead, BufReader, Read, Write};
 use std::path::Path;
 
@@ -112,6 +112,9 @@ pub enum Entry {
     Comment(String),
     Dependency(Dependency),
     EmptyLine,
+
+    /// The minimum version of Inko required by the package.
+    Inko(Version),
 }
 
 impl fmt::Display for Entry {
@@ -120,6 +123,7 @@ impl fmt::Display for Entry {
             Entry::Comment(comment) => write!(f, "#{}", comment),
             Entry::EmptyLine => Ok(()),
             Entry::Dependency(dep) => dep.fmt(f),
+            Entry::Inko(version) => write!(f, "inko {}", version),
         }
     }
 }
@@ -168,6 +172,22 @@ impl Manifest {
 
             let chunks: Vec<_> = trimmed.split(' ').collect();
 
+            if chunks[0] == "inko" {
+                if chunks.len() != 2 {
+                    return Err(format!(
+                        "The entry on line {} is invalid",
+                        lnum
+                    ));
+                }
+
+                let version = Version::parse(chunks[1]).ok_or_else(|| {
+                    format!("The Inko version on line {} is invalid", lnum)
+                })?;
+
+                manifest.entries.push(Entry::Inko(version));
+                continue;
+            }
+
             if chunks.len() != 4 {
                 return Err(format!("The entry on line {} is invalid", lnum));
             }
@@ -250,11 +270,52 @@ impl Manifest {
             .collect()
     }
 
+    /// Returns the minimum version of Inko required by the package, if any.
+    ///
+    /// If multiple versions are specified, the highest version is returned.
+    pub fn inko_version(&self) -> Option<&Version> {
+        self.entries
+            .iter()
+            .filter_map(|entry| match entry {
+                Entry::Inko(ver) => Some(ver),
+                _ => None,
+            })
+            .max()
+    }
+
+    /// Returns an error if the package requires a newer version of Inko than
+    /// `current`.
+    ///
+    /// The `package` argument describes the package the manifest belongs to,
+    /// and is included in the error message.
+    pub fn check_inko_version(
+        &self,
+        package: &str,
+        current: &Version,
+    ) -> Result<(), String> {
+        match self.inko_version() {
+            Some(required) if required > current => Err(format!(
+                "{} requires Inko {} or newer, but you're using Inko {}. \
+                Please upgrade Inko to at least version {}",
+                package, required, current, required
+            )),
+            _ => Ok(()),
+        }
+    }
+
     pub fn save<P: AsRef<Path>>(&self, path: &P) -> Result<(), String> {
         let path = path.as_ref();
-
-        File::create(path)
-            .and_then(|mut file| file.write_all(self.to_string().as_bytes()))
+        let tmp_path = path.with_extension("pkg.tmp");
+
+        // The manifest is written to a temporary file first and then moved
+        // into place, such that an interrupted write doesn't leave behind a
+        // truncated manifest.
+        File::create(&tmp_path)
+            .and_then(|mut file| {
+                file.write_all(self.to_string().as_bytes())?;
+                file.sync_all()
+            })
+            .and_then(|_| rename(&tmp_path, path))
             .map_err(|e| format!("Failed to update {}: {}", path.display(), e))
     }
 }
@@ -385,6 +446,57 @@ require https://gitlab.com/inko-lang/foo 1.2.3 633d02e92b2a96623c276b7d7fe09568f
         );
     }
 
+    #[test]
+    fn test_manifest_parse_inko_version() {
+        assert_eq!(
+            Manifest::parse(&mut "inko 0.15.0".as_bytes()),
+            Ok(Manifest { entries: vec![Entry::Inko(Version::new(0, 15, 0))] })
+        );
+        assert_eq!(
+            Manifest::parse(&mut "inko 0.15".as_bytes()),
+            Err("The Inko version on line 1 is invalid".to_string())
+        );
+        assert_eq!(
+            Manifest::parse(&mut "inko 0.15.0 foo".as_bytes()),
+            Err("The entry on line 1 is invalid".to_string())
+        );
+    }
+
+    #[test]
+    fn test_manifest_inko_version() {
+        let mut manifest = Manifest { entries: Vec::new() };
+
+        assert_eq!(manifest.inko_version(), None);
+
+        manifest.entries.push(Entry::Inko(Version::new(0, 15, 0)));
+        manifest.entries.push(Entry::Inko(Version::new(0, 16, 0)));
+        manifest.entries.push(Entry::Inko(Version::new(0, 14, 0)));
+
+        assert_eq!(manifest.inko_version(), Some(&Version::new(0, 16, 0)));
+    }
+
+    #[test]
+    fn test_manifest_check_inko_version() {
+        let manifest =
+            Manifest { entries: vec![Entry::Inko(Version::new(0, 15, 0))] };
+
+        assert!(manifest
+            .check_inko_version("foo", &Version::new(0, 15, 0))
+            .is_ok());
+        assert!(manifest
+            .check_inko_version("foo", &Version::new(1, 0, 0))
+            .is_ok());
+        assert_eq!(
+            manifest.check_inko_version("foo", &Version::new(0, 14, 2)),
+            Err("foo requires Inko 0.15.0 or newer, but you're using Inko \
+                0.14.2. Please upgrade Inko to at least version 0.15.0"
+                .to_string())
+        );
+        assert!(Manifest { entries: Vec::new() }
+            .check_inko_version("foo", &Version::new(0, 1, 0))
+            .is_ok());
+    }
+
     #[test]
     fn test_manifest_to_string() {
         let manifest = Manifest {
@@ -392,6 +504,7 @@ require https://gitlab.com/inko-lang/foo 1.2.3 633d02e92b2a96623c276b7d7fe09568f
                 Entry::Comment(" Ignore me".to_string()),
                 Entry::Comment(String::new()),
                 Entry::EmptyLine,
+                Entry::Inko(Version::new(0, 15, 0)),
                 Entry::Dependency(Dependency {
                     url: Url::new("https://gitlab.com/inko-lang/foo"),
                     name: "foo".to_string(),
@@ -410,6 +523,7 @@ require https://gitlab.com/inko-lang/foo 1.2.3 633d02e92b2a96623c276b7d7fe09568f
         let output = "# Ignore me
 #
 
+inko 0.15.0
 require https://gitlab.com/inko-lang/foo 1.2.3 abc
 require https://github.com/inko-lang/bar 4.5.6 def
 ";
@@ -49,6 +49,11 @@ impl Version {
         Self { major, minor, patch }
     }
 
+    /// Returns the version of Inko that is in use.
+    pub fn inko() -> Self {
+        Version::parse(env!("CARGO_PKG_VERSION")).unwrap()
+    }
+
     pub fn tag_name(&self) -> String {
         format!("v{}", self)
     }
@@ -178,6 +183,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_version_inko() {
+        assert_eq!(
+            Version::inko().to_string(),
+            env!("CARGO_PKG_VERSION").to_string()
+        );
+    }
+
     #[test]
     fn test_version_tag_name() {
         assert_eq!(Version::new(1, 2, 3).tag_name(), "v1.2.3".to_string());
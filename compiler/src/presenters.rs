@@ -1,5 +1,6 @@
 //! Formatters for diagnostics.
-use crate::diagnostics::{Diagnostic, Diagnostics};
+use crate::diagnostics::{Diagnostic, Diagnostics, Note};
+use ast::source_location::SourceLocation;
 use std::env::current_dir;
 use std::path::PathBuf;
 
@@ -14,6 +15,11 @@ pub(crate) trait Presenter {
 /// The resulting output looks like this:
 ///
 ///     path/to/file.inko:line:column warning(example): this is a warning
+///       path/to/file.inko:line:column note: this is a note
+///
+/// Diagnostics reporting the same problem in the same file (e.g. the same
+/// undefined symbol used in many places) are grouped together, with the
+/// additional locations presented as notes.
 pub(crate) struct TextPresenter {
     working_directory: PathBuf,
     colors: bool,
@@ -35,32 +41,66 @@ impl TextPresenter {
         Self::new(true)
     }
 
-    fn present_diagnostic(&self, diagnostic: &Diagnostic) {
+    fn present_diagnostic(
+        &self,
+        diagnostic: &Diagnostic,
+        duplicates: &[&Diagnostic],
+    ) {
         let loc = &diagnostic.location();
-        let abs_path = diagnostic.file().as_path();
-        let rel_path = abs_path
-            .strip_prefix(&self.working_directory)
-            .unwrap_or(abs_path)
-            .to_string_lossy();
-
-        let kind = if diagnostic.is_error() {
-            format!("{}({})", self.red(self.bold("error")), diagnostic.id())
+        let (level, kind) = if diagnostic.is_error() {
+            ("error", self.red(self.bold("error")))
         } else {
-            format!(
-                "{}({})",
-                self.yellow(self.bold("warning")),
-                diagnostic.id()
-            )
+            ("warning", self.yellow(self.bold("warning")))
         };
 
         eprintln!(
-            "{}:{}:{} {}: {}",
-            rel_path,
+            "{}:{}:{} {}({}): {}",
+            self.relative_path(diagnostic.file()),
             loc.lines.start(),
             loc.columns.start(),
             kind,
+            diagnostic.id(),
             diagnostic.message()
         );
+
+        for note in diagnostic.notes() {
+            self.present_note(note.file(), note.location(), note.message());
+        }
+
+        for dup in duplicates {
+            self.present_note(
+                dup.file(),
+                dup.location(),
+                &format!("the same {} is also produced here", level),
+            );
+
+            for note in dup.notes() {
+                self.present_note(note.file(), note.location(), note.message());
+            }
+        }
+    }
+
+    fn present_note(
+        &self,
+        file: &PathBuf,
+        location: &SourceLocation,
+        message: &str,
+    ) {
+        eprintln!(
+            "  {}:{}:{} {}: {}",
+            self.relative_path(file),
+            location.lines.start(),
+            location.columns.start(),
+            self.bold("note"),
+            message
+        );
+    }
+
+    fn relative_path(&self, path: &PathBuf) -> String {
+        path.strip_prefix(&self.working_directory)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .into_owned()
     }
 
     fn red<S: Into<String>>(&self, text: S) -> String {
@@ -86,8 +126,18 @@ impl TextPresenter {
 
 impl Presenter for TextPresenter {
     fn present(&self, diagnostics: &Diagnostics) {
+        let mut groups: Vec<(&Diagnostic, Vec<&Diagnostic>)> = Vec::new();
+
         for diag in diagnostics.iter() {
-            self.present_diagnostic(diag);
+            match groups.iter_mut().find(|(first, _)| first.same_problem(diag))
+            {
+                Some((_, dups)) => dups.push(diag),
+                None => groups.push((diag, Vec::new())),
+            }
+        }
+
+        for (diag, dups) in groups {
+            self.present_diagnostic(diag, &dups);
         }
     }
 }
@@ -102,9 +152,11 @@ impl JsonPresenter {
 
     fn to_json(&self, diagnostic: &Diagnostic) -> String {
         let loc = diagnostic.location();
+        let notes: Vec<_> =
+            diagnostic.notes().iter().map(|n| self.note_to_json(n)).collect();
 
         format!(
-            "{{\"id\": {:?}, \"level\": {:?}, \"file\": {:?}, \"lines\": [{}, {}], \"columns\": [{}, {}], \"message\": {:?}}}",
+            "{{\"id\": {:?}, \"level\": {:?}, \"file\": {:?}, \"lines\": [{}, {}], \"columns\": [{}, {}], \"message\": {:?}, \"notes\": [{}]}}",
             diagnostic.id().to_string(),
             diagnostic.kind().to_string(),
             diagnostic.file().to_string_lossy(),
@@ -112,7 +164,22 @@ impl JsonPresenter {
             loc.lines.end(),
             loc.columns.start(),
             loc.columns.end(),
-            diagnostic.message()
+            diagnostic.message(),
+            notes.join(",")
+        )
+    }
+
+    fn note_to_json(&self, note: &Note) -> String {
+        let loc = note.location();
+
+        format!(
+            "{{\"file\": {:?}, \"lines\": [{}, {}], \"columns\": [{}, {}], \"message\": {:?}}}",
+            note.file().to_string_lossy(),
+            loc.lines.start(),
+            loc.lines.end(),
+            loc.columns.start(),
+            loc.columns.end(),
+            note.message()
         )
     }
 }
@@ -12,11 +12,12 @@ use types::format::{format_type, format_type_with_arguments};
 use types::resolve::TypeResolver;
 use types::{
     Block, BuiltinCallInfo, CallInfo, CallKind, ClassId, ClassInstance,
-    Closure, ClosureCallInfo, ClosureId, ConstantKind, ConstantPatternKind,
-    Database, FieldId, FieldInfo, IdentifierKind, MethodId, MethodKind,
-    MethodLookup, ModuleId, Receiver, Symbol, ThrowKind, TraitId,
-    TraitInstance, TypeArguments, TypeBounds, TypeId, TypeRef, Variable,
-    VariableId, VariableLocation, CALL_METHOD, DEREF_POINTER_FIELD,
+    Closure, ClosureCallInfo, ClosureId, ConstantId, ConstantKind,
+    ConstantPatternKind, Database, DefaultValue, FieldId, FieldInfo,
+    IdentifierKind, MethodId, MethodKind, MethodLookup, ModuleId, Receiver,
+    Symbol, ThrowKind, TraitId, TraitInstance, TypeArguments, TypeBounds,
+    TypeId, TypeRef, Variable, VariableId, VariableLocation, CALL_METHOD,
+    DEREF_POINTER_FIELD,
 };
 
 const IGNORE_VARIABLE: &str = "_";
@@ -51,6 +52,27 @@ fn copy_inherited_type_arguments(
     }
 }
 
+fn sorted_symbol_names(db: &Database, module: ModuleId) -> Vec<String> {
+    let mut names: Vec<String> =
+        module.symbols(db).into_iter().map(|(name, _)| name).collect();
+
+    names.sort();
+    names
+}
+
+/// Returns the names to suggest when a method lookup on a type fails.
+fn method_names(db: &Database, receiver: TypeId) -> Vec<String> {
+    let mut names = receiver.method_names(db);
+
+    names.sort();
+
+    if let TypeId::Module(id) = receiver {
+        names.append(&mut sorted_symbol_names(db, id));
+    }
+
+    names
+}
+
 struct Pattern<'a> {
     /// The variable scope to use for defining variables introduced by patterns.
     variable_scope: &'a mut VariableScope,
@@ -169,6 +191,24 @@ impl<'a> LexicalScope<'a> {
         }
     }
 
+    /// Returns the names of the variables visible in this scope, starting with
+    /// the innermost scope.
+    fn variable_names(&self) -> Vec<String> {
+        let mut names = Vec::new();
+        let mut source = Some(self);
+
+        while let Some(current) = source {
+            let mut vars: Vec<String> =
+                current.variables.variables.keys().cloned().collect();
+
+            vars.sort();
+            names.append(&mut vars);
+            source = current.parent;
+        }
+
+        names
+    }
+
     fn in_loop(&self) -> bool {
         self.inside(ScopeKind::Loop)
     }
@@ -256,6 +296,12 @@ struct MethodCall {
     /// The named arguments that have been specified thus far.
     named_arguments: HashSet<String>,
 
+    /// If a named argument is also specified as a positional argument.
+    ///
+    /// In this case the number of arguments isn't checked, as the call is
+    /// already invalid and the error would just add noise.
+    duplicate_argument: bool,
+
     /// If input/output types should be limited to sendable types.
     require_sendable: bool,
 
@@ -397,6 +443,7 @@ impl MethodCall {
             type_arguments,
             arguments: 0,
             named_arguments: HashSet::new(),
+            duplicate_argument: false,
             require_sendable,
             check_sendable: Vec::new(),
             return_type: TypeRef::Unknown,
@@ -433,7 +480,9 @@ impl MethodCall {
     ) {
         let expected = self.method.number_of_arguments(&state.db);
 
-        if self.arguments > expected && self.method.is_variadic(&state.db) {
+        if self.duplicate_argument
+            || (self.arguments > expected && self.method.is_variadic(&state.db))
+        {
             return;
         }
 
@@ -616,19 +665,33 @@ impl<'a> DefineConstants<'a> {
         // (e.g. `A + B` or `[A, B]`) can refer to them, regardless of the order
         // in which modules are processed.
         for module in modules.iter_mut() {
-            DefineConstants { state, module: module.module_id }
-                .run(module, true);
+            DefineConstants { state, module: module.module_id }.run(module);
         }
 
-        for module in modules.iter_mut() {
-            DefineConstants { state, module: module.module_id }
-                .run(module, false);
+        // Complex constants may refer to other complex constants, possibly
+        // defined later in the same module or in a different module. To handle
+        // this we type-check them in the order of their dependencies.
+        let mut order = ConstantOrder::new(modules);
+
+        for id in order.ids.clone() {
+            order.visit(state, modules, id);
+        }
+
+        for (module, index) in order.order {
+            let module_id = modules[module].module_id;
+
+            if let hir::TopLevelExpression::Constant(ref mut node) =
+                modules[module].expressions[index]
+            {
+                DefineConstants { state, module: module_id }
+                    .define_constant(node);
+            }
         }
 
         !state.diagnostics.has_errors()
     }
 
-    fn run(mut self, module: &mut hir::Module, simple_only: bool) {
+    fn run(mut self, module: &mut hir::Module) {
         for expression in module.expressions.iter_mut() {
             let node = if let hir::TopLevelExpression::Constant(ref mut node) =
                 expression
@@ -638,7 +701,7 @@ impl<'a> DefineConstants<'a> {
                 continue;
             };
 
-            if node.value.is_simple_literal() == simple_only {
+            if node.value.is_simple_literal() {
                 self.define_constant(node);
             }
         }
@@ -657,6 +720,150 @@ impl<'a> DefineConstants<'a> {
     }
 }
 
+/// A type for determining the order in which to type-check complex constants.
+struct ConstantOrder {
+    /// The complex constants in the order they are defined in, along with the
+    /// module and expression indexes of their definitions.
+    nodes: HashMap<ConstantId, (usize, usize)>,
+
+    /// The IDs of the complex constants, in the order they are defined in.
+    ids: Vec<ConstantId>,
+
+    /// The constants that have been visited.
+    visited: HashSet<ConstantId>,
+
+    /// The constants we're currently checking the dependencies of.
+    stack: Vec<ConstantId>,
+
+    /// The module and expression indexes of the constants to type-check, in
+    /// the order to type-check them in.
+    order: Vec<(usize, usize)>,
+}
+
+impl ConstantOrder {
+    fn new(modules: &[hir::Module]) -> ConstantOrder {
+        let mut nodes = HashMap::new();
+        let mut ids = Vec::new();
+
+        for (mod_idx, module) in modules.iter().enumerate() {
+            for (expr_idx, expr) in module.expressions.iter().enumerate() {
+                if let hir::TopLevelExpression::Constant(ref node) = expr {
+                    if node.value.is_simple_literal() {
+                        continue;
+                    }
+
+                    let id = node.constant_id.unwrap();
+
+                    nodes.insert(id, (mod_idx, expr_idx));
+                    ids.push(id);
+                }
+            }
+        }
+
+        ConstantOrder {
+            nodes,
+            ids,
+            visited: HashSet::new(),
+            stack: Vec::new(),
+            order: Vec::new(),
+        }
+    }
+
+    fn visit(
+        &mut self,
+        state: &mut State,
+        modules: &[hir::Module],
+        id: ConstantId,
+    ) {
+        let (mod_idx, expr_idx) = self.nodes[&id];
+        let module = &modules[mod_idx];
+        let node = match &module.expressions[expr_idx] {
+            hir::TopLevelExpression::Constant(n) => n,
+            _ => unreachable!(),
+        };
+
+        if let Some(start) = self.stack.iter().position(|&v| v == id) {
+            state.diagnostics.error(
+                DiagnosticId::InvalidConstExpr,
+                format!(
+                    "the value of constant '{}' depends on the constant itself",
+                    node.name.name
+                ),
+                module.module_id.file(&state.db),
+                node.location.clone(),
+            );
+
+            // The constants part of the cycle are typed as errors, ensuring
+            // we don't produce any additional type errors for them.
+            for &id in &self.stack[start..] {
+                id.set_value_type(&mut state.db, TypeRef::Error);
+            }
+
+            return;
+        }
+
+        if !self.visited.insert(id) {
+            return;
+        }
+
+        let mut deps = Vec::new();
+
+        self.stack.push(id);
+        constant_dependencies(
+            &state.db,
+            module.module_id,
+            &node.value,
+            &mut deps,
+        );
+
+        for dep in deps {
+            if self.nodes.contains_key(&dep) {
+                self.visit(state, modules, dep);
+            }
+        }
+
+        self.stack.pop();
+
+        if id.value_type(&state.db) != TypeRef::Error {
+            self.order.push((mod_idx, expr_idx));
+        }
+    }
+}
+
+fn constant_dependencies(
+    db: &Database,
+    module: ModuleId,
+    node: &hir::ConstExpression,
+    dependencies: &mut Vec<ConstantId>,
+) {
+    match node {
+        hir::ConstExpression::Binary(ref n) => {
+            constant_dependencies(db, module, &n.left, dependencies);
+            constant_dependencies(db, module, &n.right, dependencies);
+        }
+        hir::ConstExpression::Array(ref n) => {
+            for val in &n.values {
+                constant_dependencies(db, module, val, dependencies);
+            }
+        }
+        hir::ConstExpression::ConstantRef(ref n) => {
+            let symbol = if let Some(src) = n.source.as_ref() {
+                match module.symbol(db, &src.name) {
+                    Some(Symbol::Module(m)) => m.symbol(db, &n.name),
+                    _ => None,
+                }
+            } else {
+                module.symbol(db, &n.name)
+            };
+
+            if let Some(Symbol::Constant(id)) = symbol {
+                dependencies.push(id);
+            }
+        }
+        _ => {}
+    }
+}
+
 /// A compiler pass for type-checking expressions in methods.
 pub(crate) struct Expressions<'a> {
     state: &'a mut State,
@@ -1006,6 +1213,7 @@ impl<'a> CheckConstant<'a> {
             hir::ConstExpression::Binary(ref mut n) => self.binary(n),
             hir::ConstExpression::ConstantRef(ref mut n) => self.constant(n),
             hir::ConstExpression::Array(ref mut n) => self.array(n),
+            hir::ConstExpression::Bytes(ref mut n) => self.bytes_literal(n),
         }
     }
 
@@ -1035,6 +1243,15 @@ impl<'a> CheckConstant<'a> {
         node.resolved_type
     }
 
+    fn bytes_literal(&mut self, node: &mut hir::ConstBytesLiteral) -> TypeRef {
+        // Mutating constants isn't safe, so embedded bytes are typed as `ref
+        // ByteArray` instead of `ByteArray`.
+        node.resolved_type = TypeRef::Ref(TypeId::ClassInstance(
+            ClassInstance::new(ClassId::byte_array()),
+        ));
+        node.resolved_type
+    }
+
     fn true_literal(&mut self, node: &mut hir::True) -> TypeRef {
         node.resolved_type = TypeRef::boolean();
         node.resolved_type
@@ -1220,6 +1437,12 @@ impl<'a> CheckConstant<'a> {
                     self.file(),
                     location.clone(),
                 );
+                self.state.diagnostics.suggest_name(
+                    name,
+                    method_names(self.db(), rec_id),
+                    self.file(),
+                    location.clone(),
+                );
             }
         }
 
@@ -2305,6 +2528,7 @@ impl<'a> CheckMethodBody<'a> {
                     self.file(),
                     location.clone(),
                 );
+                self.suggest_variable(name, scope, location);
 
                 return None;
             };
@@ -2544,6 +2768,8 @@ impl<'a> CheckMethodBody<'a> {
                             self.file(),
                             node.location.clone(),
                         );
+                        self.suggest_symbol(&node.name, scope, &node.location);
+                        self.suggest_import(&node.name, &node.location);
 
                         return TypeRef::Error;
                     }
@@ -2666,6 +2892,8 @@ impl<'a> CheckMethodBody<'a> {
                             self.file(),
                             node.location.clone(),
                         );
+                        self.suggest_symbol(name, scope, &node.location);
+                        self.suggest_import(name, &node.location);
 
                         return TypeRef::Error;
                     }
@@ -3261,6 +3489,7 @@ impl<'a> CheckMethodBody<'a> {
                             self.file(),
                             node.location.clone(),
                         );
+                        self.suggest_method(&setter, rec_id, &node.location);
 
                         TypeRef::Error
                     }
@@ -3619,6 +3848,11 @@ impl<'a> CheckMethodBody<'a> {
                             self.file(),
                             node.location.clone(),
                         );
+                        self.suggest_method(
+                            &node.name.name,
+                            rec_id,
+                            &node.location,
+                        );
 
                         TypeRef::Error
                     }
@@ -3631,6 +3865,7 @@ impl<'a> CheckMethodBody<'a> {
                     self.file(),
                     node.location.clone(),
                 );
+                self.suggest_method(&node.name.name, rec_id, &node.location);
 
                 return TypeRef::Error;
             }
@@ -3647,7 +3882,12 @@ impl<'a> CheckMethodBody<'a> {
 
         call.check_mutability(self.state, &node.location);
         call.check_type_bounds(self.state, &node.location);
-        self.call_arguments(&mut node.arguments, &mut call, scope);
+        self.call_arguments(
+            &mut node.arguments,
+            &mut call,
+            scope,
+            &node.location,
+        );
         call.check_arguments(self.state, &node.location);
         call.resolve_return_type(self.state);
         call.check_sendable(self.state, &node.location);
@@ -3732,6 +3972,8 @@ impl<'a> CheckMethodBody<'a> {
                                 self.file(),
                                 node.location.clone(),
                             );
+                            self.suggest_symbol(name, scope, &node.location);
+                            self.suggest_import(name, &node.location);
 
                             return TypeRef::Error;
                         }
@@ -3750,7 +3992,12 @@ impl<'a> CheckMethodBody<'a> {
 
         call.check_mutability(self.state, &node.location);
         call.check_type_bounds(self.state, &node.location);
-        self.call_arguments(&mut node.arguments, &mut call, scope);
+        self.call_arguments(
+            &mut node.arguments,
+            &mut call,
+            scope,
+            &node.location,
+        );
         call.check_arguments(self.state, &node.location);
         call.resolve_return_type(self.state);
         call.check_sendable(self.state, &node.location);
@@ -4245,9 +4492,10 @@ impl<'a> CheckMethodBody<'a> {
 
     fn call_arguments(
         &mut self,
-        nodes: &mut [hir::Argument],
+        nodes: &mut Vec<hir::Argument>,
         call: &mut MethodCall,
         scope: &mut LexicalScope,
+        location: &SourceLocation,
     ) {
         for (index, arg) in nodes.iter_mut().enumerate() {
             match arg {
@@ -4264,6 +4512,78 @@ impl<'a> CheckMethodBody<'a> {
                 }
             }
         }
+
+        self.default_arguments(nodes, call, scope, location);
+    }
+
+    fn default_arguments(
+        &mut self,
+        nodes: &mut Vec<hir::Argument>,
+        call: &mut MethodCall,
+        scope: &mut LexicalScope,
+        location: &SourceLocation,
+    ) {
+        let positional = call.arguments - call.named_arguments.len();
+
+        // Arguments that aren't specified but have a default value are added
+        // as named arguments, such that they're lowered to MIR the same way as
+        // arguments specified explicitly.
+        for arg in call.method.arguments(self.db()).into_iter().skip(positional)
+        {
+            let default = match arg.default {
+                Some(val) if !call.named_arguments.contains(&arg.name) => val,
+                _ => continue,
+            };
+            let loc = location.clone();
+            let value = match default {
+                DefaultValue::Int(value) => {
+                    hir::Expression::Int(Box::new(hir::IntLiteral {
+                        value,
+                        resolved_type: TypeRef::Unknown,
+                        location: loc.clone(),
+                    }))
+                }
+                DefaultValue::Float(value) => {
+                    hir::Expression::Float(Box::new(hir::FloatLiteral {
+                        value,
+                        resolved_type: TypeRef::Unknown,
+                        location: loc.clone(),
+                    }))
+                }
+                DefaultValue::String(value) => {
+                    hir::Expression::String(Box::new(hir::StringLiteral {
+                        values: vec![hir::StringValue::Text(Box::new(
+                            hir::StringText { value, location: loc.clone() },
+                        ))],
+                        resolved_type: TypeRef::Unknown,
+                        location: loc.clone(),
+                    }))
+                }
+                DefaultValue::Bool(true) => {
+                    hir::Expression::True(Box::new(hir::True {
+                        resolved_type: TypeRef::Unknown,
+                        location: loc.clone(),
+                    }))
+                }
+                DefaultValue::Bool(false) => {
+                    hir::Expression::False(Box::new(hir::False {
+                        resolved_type: TypeRef::Unknown,
+                        location: loc.clone(),
+                    }))
+                }
+            };
+
+            let mut node = hir::NamedArgument {
+                index: arg.index,
+                name: hir::Identifier { name: arg.name, location: loc.clone() },
+                value,
+                expected_type: TypeRef::Unknown,
+                location: loc,
+            };
+
+            node.expected_type = self.named_argument(call, &mut node, scope);
+            nodes.push(hir::Argument::Named(Box::new(node)));
+        }
     }
 
     fn positional_argument(
@@ -4302,6 +4622,27 @@ impl<'a> CheckMethodBody<'a> {
         if let Some((index, expected)) =
             call.method.named_argument(self.db(), name)
         {
+            // Positional arguments always come before named arguments, so
+            // the number of positional arguments is the number of arguments
+            // processed thus far, minus the named ones.
+            let positional = call.arguments - call.named_arguments.len();
+
+            if index < positional {
+                self.state.diagnostics.error(
+                    DiagnosticId::InvalidCall,
+                    format!(
+                        "the argument '{}' is already specified as a \
+                        positional argument",
+                        name
+                    ),
+                    self.file(),
+                    node.name.location.clone(),
+                );
+
+                call.duplicate_argument = true;
+                return TypeRef::Error;
+            }
+
             // We persist the index so we don't need to look it up again when
             // lowering to MIR.
             node.index = index;
@@ -4475,6 +4816,90 @@ impl<'a> CheckMethodBody<'a> {
         );
     }
 
+    fn suggest_method(
+        &mut self,
+        name: &str,
+        receiver: TypeId,
+        location: &SourceLocation,
+    ) {
+        let names = method_names(self.db(), receiver);
+
+        self.state.diagnostics.suggest_name(
+            name,
+            names,
+            self.file(),
+            location.clone(),
+        );
+    }
+
+    fn suggest_variable(
+        &mut self,
+        name: &str,
+        scope: &LexicalScope,
+        location: &SourceLocation,
+    ) {
+        let names = scope.variable_names();
+
+        self.state.diagnostics.suggest_name(
+            name,
+            names,
+            self.file(),
+            location.clone(),
+        );
+    }
+
+    /// Suggests a name similar to an undefined symbol.
+    ///
+    /// Names are ranked by scope: local variables come first (starting with the
+    /// innermost scope), followed by the methods of `self`, followed by the
+    /// symbols of the surrounding module.
+    fn suggest_symbol(
+        &mut self,
+        name: &str,
+        scope: &LexicalScope,
+        location: &SourceLocation,
+    ) {
+        let mut names = scope.variable_names();
+
+        if let Ok(id) = scope.surrounding_type.type_id(self.db()) {
+            let mut methods = id.method_names(self.db());
+
+            methods.sort();
+            names.append(&mut methods);
+        }
+
+        names.append(&mut sorted_symbol_names(self.db(), self.module));
+        self.state.diagnostics.suggest_name(
+            name,
+            names,
+            self.file(),
+            location.clone(),
+        );
+    }
+
+    /// Suggests imports for an undefined symbol, based on the modules that
+    /// define a type or are named after the symbol.
+    fn suggest_import(&mut self, name: &str, location: &SourceLocation) {
+        let mut imports: Vec<String> = self
+            .db()
+            .modules_named(name)
+            .into_iter()
+            .map(|id| id.name(self.db()).to_string())
+            .collect();
+
+        for id in self.db().modules_defining_type(name) {
+            imports.push(format!("{} ({})", id.name(self.db()), name));
+        }
+
+        for import in imports {
+            self.state.diagnostics.suggest_import(
+                import,
+                self.file(),
+                location.clone(),
+            );
+        }
+    }
+
     fn private_method_call(&mut self, name: &str, location: &SourceLocation) {
         self.state.diagnostics.private_method_call(
             name,
@@ -28,6 +28,14 @@ impl<'a> DefineImportedTypes<'a> {
         state: &'a mut State,
         modules: &mut Vec<hir::Module>,
     ) -> bool {
+        // Re-exports are defined first, such that importing re-exported
+        // symbols works regardless of the order in which modules are
+        // processed.
+        for module in modules.iter() {
+            DefineImportedTypes { state, module: module.module_id }
+                .define_reexports(module);
+        }
+
         for module in modules {
             DefineImportedTypes { state, module: module.module_id }.run(module);
         }
@@ -35,6 +43,46 @@ impl<'a> DefineImportedTypes<'a> {
         !state.diagnostics.has_errors()
     }
 
+    fn define_reexports(&mut self, module: &hir::Module) {
+        for expr in &module.expressions {
+            let node = match expr {
+                hir::TopLevelExpression::Import(ref n) if n.public => n,
+                _ => continue,
+            };
+
+            if node.symbols.is_empty() {
+                self.invalid_reexport(node.location.clone());
+                continue;
+            }
+
+            let source_name = self.import_source(&node.source);
+            let source = self.db().module(&source_name.to_string());
+
+            for symbol in &node.symbols {
+                if symbol.name.name == IMPORT_MODULE_ITSELF_NAME {
+                    self.invalid_reexport(symbol.location.clone());
+                    continue;
+                }
+
+                self.module.add_reexport(
+                    self.db_mut(),
+                    symbol.import_as.name.clone(),
+                    source,
+                    symbol.name.name.clone(),
+                );
+            }
+        }
+    }
+
+    fn invalid_reexport(&mut self, location: SourceLocation) {
+        self.state.diagnostics.error(
+            DiagnosticId::InvalidSymbol,
+            "modules can't be re-exported, only the symbols they define",
+            self.file(),
+            location,
+        );
+    }
+
     fn run(mut self, module: &mut hir::Module) {
         for expr in &module.expressions {
             if let hir::TopLevelExpression::Import(ref node) = expr {
@@ -201,6 +249,7 @@ mod tests {
                     location: cols(1, 1),
                 }],
                 symbols: Vec::new(),
+                public: false,
                 location: cols(1, 1),
             }))],
         )];
@@ -236,6 +285,7 @@ mod tests {
                         location: cols(1, 1),
                     }],
                     symbols: Vec::new(),
+                    public: false,
                     location: cols(1, 1),
                 })),
                 hir::TopLevelExpression::Import(Box::new(hir::Import {
@@ -244,6 +294,7 @@ mod tests {
                         location: cols(3, 3),
                     }],
                     symbols: Vec::new(),
+                    public: false,
                     location: cols(2, 2),
                 })),
             ],
@@ -282,6 +333,7 @@ mod tests {
                     },
                     location: cols(1, 1),
                 }],
+                public: false,
                 location: cols(1, 1),
             }))],
         )];
@@ -327,6 +379,7 @@ mod tests {
                     },
                     location: cols(1, 1),
                 }],
+                public: false,
                 location: cols(1, 1),
             }))],
         )];
@@ -384,6 +437,7 @@ mod tests {
                         location: cols(1, 1),
                     },
                 ],
+                public: false,
                 location: cols(1, 1),
             }))],
         )];
@@ -422,6 +476,7 @@ mod tests {
                     },
                     location: cols(1, 1),
                 }],
+                public: false,
                 location: cols(1, 1),
             }))],
         )];
@@ -472,6 +527,7 @@ mod tests {
                     },
                     location: cols(1, 1),
                 }],
+                public: false,
                 location: cols(1, 1),
             }))],
         )];
@@ -541,6 +597,7 @@ mod tests {
                         location: cols(2, 2),
                     },
                 ],
+                public: false,
                 location: cols(1, 2),
             }))],
         )];
@@ -601,6 +658,7 @@ mod tests {
                         location: cols(2, 2),
                     },
                 ],
+                public: false,
                 location: cols(1, 2),
             }))],
         )];
@@ -648,6 +706,7 @@ mod tests {
                     },
                     location: cols(2, 2),
                 }],
+                public: false,
                 location: cols(1, 2),
             }))],
         )];
@@ -686,6 +745,7 @@ mod tests {
                     },
                     location: cols(1, 1),
                 }],
+                public: false,
                 location: cols(1, 1),
             }))],
         )];
@@ -739,6 +799,7 @@ mod tests {
                         },
                         location: cols(1, 1),
                     }],
+                    public: false,
                     location: cols(1, 1),
                 }))],
             ),
@@ -761,6 +822,7 @@ mod tests {
                         },
                         location: cols(1, 1),
                     }],
+                    public: false,
                     location: cols(1, 1),
                 }))],
             ),
@@ -790,4 +852,87 @@ mod tests {
         assert_eq!(error.file(), &PathBuf::from("test.inko"));
         assert_eq!(error.location(), &cols(4, 4));
     }
+
+    #[test]
+    fn test_import_reexported_symbol() {
+        let symbol = "fizz".to_string();
+        let mut state = State::new(Config::new());
+        let mut modules = vec![
+            hir_module(
+                &mut state,
+                ModuleName::new("bar"),
+                vec![hir::TopLevelExpression::Import(Box::new(hir::Import {
+                    source: vec![hir::Identifier {
+                        name: "foo".to_string(),
+                        location: cols(1, 1),
+                    }],
+                    symbols: vec![hir::ImportSymbol {
+                        name: hir::Identifier {
+                            name: symbol.clone(),
+                            location: cols(4, 4),
+                        },
+                        import_as: hir::Identifier {
+                            name: symbol.clone(),
+                            location: cols(1, 1),
+                        },
+                        location: cols(1, 1),
+                    }],
+                    public: false,
+                    location: cols(1, 1),
+                }))],
+            ),
+            hir_module(
+                &mut state,
+                ModuleName::new("foo"),
+                vec![hir::TopLevelExpression::Import(Box::new(hir::Import {
+                    source: vec![hir::Identifier {
+                        name: "fizz".to_string(),
+                        location: cols(1, 1),
+                    }],
+                    symbols: vec![hir::ImportSymbol {
+                        name: hir::Identifier {
+                            name: symbol.clone(),
+                            location: cols(4, 4),
+                        },
+                        import_as: hir::Identifier {
+                            name: symbol.clone(),
+                            location: cols(1, 1),
+                        },
+                        location: cols(1, 1),
+                    }],
+                    public: true,
+                    location: cols(1, 1),
+                }))],
+            ),
+        ];
+
+        let fizz_mod = Module::alloc(
+            &mut state.db,
+            ModuleName::new("fizz"),
+            "fizz.inko".into(),
+        );
+
+        let fizz = Method::alloc(
+            &mut state.db,
+            fizz_mod,
+            symbol.clone(),
+            Visibility::Public,
+            MethodKind::Instance,
+        );
+
+        fizz_mod.new_symbol(
+            &mut state.db,
+            symbol.clone(),
+            Symbol::Method(fizz),
+        );
+
+        assert!(DefineImportedTypes::run_all(&mut state, &mut modules));
+
+        let bar_mod = modules[0].module_id;
+
+        assert_eq!(
+            bar_mod.symbol(&state.db, &symbol),
+            Some(Symbol::Method(fizz))
+        );
+    }
 }
@@ -10,10 +10,10 @@ use std::path::PathBuf;
 use types::check::{Environment, TypeChecker};
 use types::format::{format_type, format_type_with_arguments};
 use types::{
-    Block, ClassId, ClassInstance, Database, Method, MethodId, MethodKind,
-    MethodSource, ModuleId, Symbol, TraitId, TraitInstance, TypeArguments,
-    TypeBounds, TypeId, TypeRef, VariableLocation, Visibility, DROP_METHOD,
-    MAIN_CLASS, MAIN_METHOD,
+    Block, ClassId, ClassInstance, Database, DefaultValue, Method, MethodId,
+    MethodKind, MethodSource, ModuleId, Symbol, TraitId, TraitInstance,
+    TypeArguments, TypeBounds, TypeId, TypeRef, VariableLocation, Visibility,
+    DROP_METHOD, MAIN_CLASS, MAIN_METHOD,
 };
 
 fn method_kind(kind: hir::MethodKind) -> MethodKind {
@@ -168,7 +168,9 @@ trait MethodDefiner {
             );
         }
 
-        for node in nodes {
+        let mut require_default = false;
+
+        for (index, node) in nodes.iter_mut().enumerate() {
             let arg_type = self.type_check(&mut node.value_type, rules, scope);
 
             if require_send && !arg_type.is_sendable(self.db()) {
@@ -198,9 +200,93 @@ trait MethodDefiner {
                 arg_type,
                 var_loc,
             );
+
+            if let Some(value) = node.default.as_ref() {
+                if let Some(val) = self.default_value(value, arg_type) {
+                    method.set_argument_default(self.db_mut(), index, val);
+                }
+
+                require_default = true;
+            } else if require_default {
+                let file = self.file();
+
+                self.state_mut().diagnostics.error(
+                    DiagnosticId::InvalidMethod,
+                    format!(
+                        "the argument '{}' requires a default value, as it \
+                        follows an argument with a default value",
+                        node.name.name
+                    ),
+                    file,
+                    node.location.clone(),
+                );
+            }
         }
     }
 
+    fn default_value(
+        &mut self,
+        node: &hir::Expression,
+        expected: TypeRef,
+    ) -> Option<DefaultValue> {
+        let (value, given) = match node {
+            hir::Expression::Int(n) => {
+                (DefaultValue::Int(n.value), TypeRef::int())
+            }
+            hir::Expression::Float(n) => {
+                (DefaultValue::Float(n.value), TypeRef::float())
+            }
+            hir::Expression::String(n) => match n.values.as_slice() {
+                [] => (DefaultValue::String(String::new()), TypeRef::string()),
+                [hir::StringValue::Text(v)] => {
+                    (DefaultValue::String(v.value.clone()), TypeRef::string())
+                }
+                _ => return self.invalid_default_value(node),
+            },
+            hir::Expression::True(_) => {
+                (DefaultValue::Bool(true), TypeRef::boolean())
+            }
+            hir::Expression::False(_) => {
+                (DefaultValue::Bool(false), TypeRef::boolean())
+            }
+            _ => return self.invalid_default_value(node),
+        };
+
+        if TypeChecker::check(self.db(), given, expected) {
+            return Some(value);
+        }
+
+        let given = format_type(self.db(), given);
+        let expected = format_type(self.db(), expected);
+        let file = self.file();
+
+        self.state_mut().diagnostics.type_error(
+            given,
+            expected,
+            file,
+            node.location().clone(),
+        );
+
+        None
+    }
+
+    fn invalid_default_value(
+        &mut self,
+        node: &hir::Expression,
+    ) -> Option<DefaultValue> {
+        let file = self.file();
+
+        self.state_mut().diagnostics.error(
+            DiagnosticId::InvalidMethod,
+            "default argument values must be Int, Float, String or Bool \
+            literals",
+            file,
+            node.location().clone(),
+        );
+
+        None
+    }
+
     fn define_return_type(
         &mut self,
         node: Option<&mut hir::Type>,
@@ -583,6 +669,17 @@ impl<'a> DefineMethods<'a> {
         };
 
         for arg in &mut node.arguments {
+            if let Some(val) = arg.default.as_ref() {
+                let file = self.file();
+
+                self.state_mut().diagnostics.error(
+                    DiagnosticId::InvalidMethod,
+                    "external functions don't support default argument values",
+                    file,
+                    val.location().clone(),
+                );
+            }
+
             let name = arg.name.name.clone();
             let typ = self.type_check(&mut arg.value_type, rules, &scope);
             let loc = VariableLocation::from_ranges(
@@ -881,6 +978,13 @@ impl<'a> DefineMethods<'a> {
         let receiver = receiver_type(self.db(), self_type, node.kind);
 
         method.set_receiver(self.db_mut(), receiver);
+        method.set_location(
+            self.db_mut(),
+            VariableLocation::from_ranges(
+                &node.location.lines,
+                &node.location.columns,
+            ),
+        );
 
         let scope = TypeScope::with_bounds(
             self.module,
@@ -948,6 +1052,13 @@ impl<'a> DefineMethods<'a> {
         let receiver = receiver_type(self.db(), self_type, node.kind);
 
         method.set_receiver(self.db_mut(), receiver);
+        method.set_location(
+            self.db_mut(),
+            VariableLocation::from_ranges(
+                &node.location.lines,
+                &node.location.columns,
+            ),
+        );
 
         let scope = TypeScope::with_bounds(
             self.module,
@@ -1198,6 +1309,7 @@ impl<'a> ImplementTraitMethods<'a> {
                 file,
                 node.location.clone(),
             );
+            self.note_trait_method(method);
         }
 
         let bounds = class_id
@@ -1254,6 +1366,23 @@ impl<'a> ImplementTraitMethods<'a> {
         }
     }
 
+    /// Adds a note to the last diagnostic, pointing to the definition of a
+    /// trait method.
+    fn note_trait_method(&mut self, method: MethodId) {
+        if let Some(loc) = method.location(self.db()) {
+            let file = method.module(self.db()).file(self.db());
+
+            self.state_mut().diagnostics.note(
+                "the trait method is defined here",
+                file,
+                SourceLocation::new(
+                    loc.line..=loc.line,
+                    loc.start_column..=loc.end_column,
+                ),
+            );
+        }
+    }
+
     fn implement_method(
         &mut self,
         node: &mut hir::DefineInstanceMethod,
@@ -1356,6 +1485,7 @@ impl<'a> ImplementTraitMethods<'a> {
                 file,
                 node.location.clone(),
             );
+            self.note_trait_method(original);
         }
 
         if trait_instance.instance_of() == self.drop_trait
@@ -16,6 +16,7 @@ pub(crate) mod define_types;
 pub(crate) mod expressions;
 pub(crate) mod imports;
 pub(crate) mod methods;
+pub(crate) mod recursion;
 
 #[derive(Eq, PartialEq)]
 enum RefKind {
@@ -99,6 +100,35 @@ impl<'a> TypeScope<'a> {
                 .or_else(|| self.module.symbol(db, name))
         }
     }
+
+    /// Returns the names of the types available in this scope, ordered from
+    /// the innermost to the outermost scope.
+    pub(crate) fn type_names(&self, db: &Database) -> Vec<String> {
+        let mut params =
+            self.method.map(|id| id.type_parameters(db)).unwrap_or_default();
+
+        match self.self_type {
+            TypeId::Class(id) => params.append(&mut id.type_parameters(db)),
+            TypeId::Trait(id) => params.append(&mut id.type_parameters(db)),
+            _ => {}
+        }
+
+        let mut names: Vec<String> =
+            params.into_iter().map(|id| id.name(db).clone()).collect();
+        let mut types: Vec<String> = self
+            .module
+            .symbols(db)
+            .into_iter()
+            .filter(|(_, sym)| {
+                matches!(sym, Symbol::Class(_) | Symbol::Trait(_))
+            })
+            .map(|(name, _)| name)
+            .collect();
+
+        types.sort();
+        names.append(&mut types);
+        names
+    }
 }
 
 /// Rules to apply when defining and checking the types of type signatures.
@@ -556,6 +586,23 @@ impl<'a> DefineTypeSignature<'a> {
                         self.file(),
                         location.clone(),
                     );
+                    self.state.diagnostics.suggest_name(
+                        name,
+                        self.scope.type_names(self.db()),
+                        self.file(),
+                        location.clone(),
+                    );
+
+                    for id in self.db().modules_defining_type(name) {
+                        let import =
+                            format!("{} ({})", id.name(self.db()), name);
+
+                        self.state.diagnostics.suggest_import(
+                            import,
+                            self.file(),
+                            location.clone(),
+                        );
+                    }
 
                     None
                 }
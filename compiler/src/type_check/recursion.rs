@@ -0,0 +1,462 @@
+//! An opt-in lint for detecting potentially unbounded recursion.
+//!
+//! Recursion that isn't bounded eventually exhausts the stack, and the only
+//! feedback a user gets is a crash at runtime. This pass looks for methods that
+//! call themselves in a non-tail position, without any of the arguments
+//! decreasing in value or size, and produces a warning for such calls.
+//!
+//! The analysis is a heuristic: an argument is considered to be decreasing if
+//! it's the result of a subtraction, division or shift (e.g. `n - 1`), or the
+//! result of a method call or field access on anything but `self` (e.g.
+//! `node.next`). Recursive calls on a receiver other than `self` (e.g.
+//! `@left.size`) are considered to be structural recursion and are ignored,
+//! as are recursive calls inside closures.
+use crate::diagnostics::DiagnosticId;
+use crate::hir;
+use crate::state::State;
+use ast::source_location::SourceLocation;
+use std::collections::HashSet;
+use types::{
+    CallKind, IdentifierKind, MethodId, ModuleId, Receiver, VariableId,
+};
+
+/// The names of the binary operator methods that produce a "smaller" value.
+const DECREASING_OPERATORS: [&str; 4] = ["-", "/", ">>", ">>>"];
+
+fn operator(name: &str) -> bool {
+    name.starts_with(|c: char| !c.is_alphanumeric() && c != '_')
+}
+
+/// A compiler pass that warns about potentially unbounded recursion.
+pub(crate) struct CheckRecursion<'a> {
+    state: &'a mut State,
+    module: ModuleId,
+}
+
+impl<'a> CheckRecursion<'a> {
+    pub(crate) fn run_all(state: &'a mut State, modules: &[hir::Module]) {
+        for module in modules {
+            CheckRecursion { state, module: module.module_id }.run(module);
+        }
+    }
+
+    fn run(&mut self, module: &hir::Module) {
+        for expr in &module.expressions {
+            match expr {
+                hir::TopLevelExpression::Class(ref n) => {
+                    for node in &n.body {
+                        match node {
+                            hir::ClassExpression::InstanceMethod(ref n) => {
+                                self.method(n.method_id, &n.body);
+                            }
+                            hir::ClassExpression::StaticMethod(ref n) => {
+                                self.method(n.method_id, &n.body);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                hir::TopLevelExpression::Reopen(ref n) => {
+                    for node in &n.body {
+                        match node {
+                            hir::ReopenClassExpression::InstanceMethod(
+                                ref n,
+                            ) => {
+                                self.method(n.method_id, &n.body);
+                            }
+                            hir::ReopenClassExpression::StaticMethod(ref n) => {
+                                self.method(n.method_id, &n.body);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                hir::TopLevelExpression::Trait(ref n) => {
+                    for node in &n.body {
+                        if let hir::TraitExpression::InstanceMethod(ref n) =
+                            node
+                        {
+                            self.method(n.method_id, &n.body);
+                        }
+                    }
+                }
+                hir::TopLevelExpression::Implement(ref n) => {
+                    for n in &n.body {
+                        self.method(n.method_id, &n.body);
+                    }
+                }
+                hir::TopLevelExpression::ModuleMethod(ref n) => {
+                    self.method(n.method_id, &n.body);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn method(&mut self, id: Option<MethodId>, body: &[hir::Expression]) {
+        let id = match id {
+            Some(id) => id,
+            None => return,
+        };
+        let mut calls = Calls::new(id);
+
+        calls.expressions(body, true);
+
+        for loc in calls.locations {
+            self.state.diagnostics.warn(
+                DiagnosticId::UnboundedRecursion,
+                format!(
+                    "the method '{}' calls itself recursively without any of \
+                    its arguments decreasing, which may exhaust the stack",
+                    id.name(&self.state.db)
+                ),
+                self.module.file(&self.state.db),
+                loc,
+            );
+        }
+    }
+}
+
+/// A type that collects the locations of unbounded recursive calls in a
+/// method body.
+struct Calls {
+    method: MethodId,
+
+    /// Variables assigned a decreasing value, such as `let m = n - 1`.
+    decreasing: HashSet<VariableId>,
+
+    /// The locations of the recursive calls found.
+    locations: Vec<SourceLocation>,
+}
+
+impl Calls {
+    fn new(method: MethodId) -> Calls {
+        Calls { method, decreasing: HashSet::new(), locations: Vec::new() }
+    }
+
+    fn expressions(&mut self, nodes: &[hir::Expression], tail: bool) {
+        let max = nodes.len().saturating_sub(1);
+
+        for (index, node) in nodes.iter().enumerate() {
+            self.expression(node, tail && index == max);
+        }
+    }
+
+    fn expression(&mut self, node: &hir::Expression, tail: bool) {
+        match node {
+            hir::Expression::Call(ref n) => {
+                if let Some(rec) = n.receiver.as_ref() {
+                    self.expression(rec, false);
+                }
+
+                for arg in &n.arguments {
+                    self.expression(arg.value(), false);
+                }
+
+                if !tail && self.unbounded_call(n) {
+                    self.locations.push(n.location.clone());
+                }
+            }
+            hir::Expression::IdentifierRef(ref n) => {
+                if let IdentifierKind::Method(ref info) = n.kind {
+                    if !tail && info.id == self.method {
+                        self.locations.push(n.location.clone());
+                    }
+                }
+            }
+            hir::Expression::And(ref n) => {
+                self.expression(&n.left, false);
+                self.expression(&n.right, false);
+            }
+            hir::Expression::Or(ref n) => {
+                self.expression(&n.left, false);
+                self.expression(&n.right, false);
+            }
+            hir::Expression::AssignField(ref n) => {
+                self.expression(&n.value, false);
+            }
+            hir::Expression::ReplaceField(ref n) => {
+                self.expression(&n.value, false);
+            }
+            hir::Expression::AssignSetter(ref n) => {
+                self.expression(&n.receiver, false);
+                self.expression(&n.value, false);
+            }
+            hir::Expression::AssignVariable(ref n) => {
+                self.expression(&n.value, false);
+                self.define(n.variable_id, &n.value);
+            }
+            hir::Expression::ReplaceVariable(ref n) => {
+                self.expression(&n.value, false);
+                self.define(n.variable_id, &n.value);
+            }
+            hir::Expression::BuiltinCall(ref n) => {
+                self.expressions(&n.arguments, false);
+            }
+            hir::Expression::DefineVariable(ref n) => {
+                self.expression(&n.value, false);
+                self.define(n.variable_id, &n.value);
+            }
+            hir::Expression::Loop(ref n) => {
+                self.expressions(&n.body, false);
+            }
+            hir::Expression::Match(ref n) => {
+                self.expression(&n.expression, false);
+
+                for case in &n.cases {
+                    if let Some(guard) = case.guard.as_ref() {
+                        self.expression(guard, false);
+                    }
+
+                    self.expressions(&case.body, tail);
+                }
+            }
+            hir::Expression::Mut(ref n) => {
+                self.expression(&n.value, false);
+            }
+            hir::Expression::Ref(ref n) => {
+                self.expression(&n.value, false);
+            }
+            hir::Expression::Recover(ref n) => {
+                self.expressions(&n.body, false);
+            }
+            hir::Expression::Return(ref n) => {
+                if let Some(val) = n.value.as_ref() {
+                    self.expression(val, true);
+                }
+            }
+            hir::Expression::Scope(ref n) => {
+                self.expressions(&n.body, tail);
+            }
+            hir::Expression::Throw(ref n) => {
+                self.expression(&n.value, false);
+            }
+            hir::Expression::Tuple(ref n) => {
+                self.expressions(&n.values, false);
+            }
+            hir::Expression::TypeCast(ref n) => {
+                self.expression(&n.value, false);
+            }
+            hir::Expression::Try(ref n) => {
+                self.expression(&n.expression, false);
+            }
+            // Closures aren't necessarily called, and literals and the like
+            // don't contain any calls.
+            _ => {}
+        }
+    }
+
+    fn define(&mut self, id: Option<VariableId>, value: &hir::Expression) {
+        if let Some(id) = id {
+            if self.decreasing(value) {
+                self.decreasing.insert(id);
+            }
+        }
+    }
+
+    fn unbounded_call(&self, node: &hir::Call) -> bool {
+        let info = match node.kind {
+            CallKind::Call(ref info) if info.id == self.method => info,
+            _ => return false,
+        };
+        let same_receiver = matches!(info.receiver, Receiver::Class)
+            || matches!(
+                node.receiver,
+                None | Some(hir::Expression::SelfObject(_))
+            );
+
+        same_receiver
+            && !node.arguments.iter().any(|a| self.decreasing(a.value()))
+    }
+
+    fn decreasing(&self, node: &hir::Expression) -> bool {
+        match node {
+            // Binary operators are calls with the left operand as the
+            // receiver, so for these only the operator itself determines if
+            // the value decreases.
+            hir::Expression::Call(ref n) if operator(&n.name.name) => {
+                DECREASING_OPERATORS.contains(&n.name.name.as_str())
+            }
+            hir::Expression::Call(ref n) => !matches!(
+                n.receiver,
+                None | Some(hir::Expression::SelfObject(_))
+            ),
+            hir::Expression::IdentifierRef(ref n) => match n.kind {
+                IdentifierKind::Variable(id) => self.decreasing.contains(&id),
+                _ => false,
+            },
+            hir::Expression::Ref(ref n) => self.decreasing(&n.value),
+            hir::Expression::Mut(ref n) => self.decreasing(&n.value),
+            hir::Expression::Recover(ref n) => {
+                n.body.last().map_or(false, |n| self.decreasing(n))
+            }
+            hir::Expression::Scope(ref n) => {
+                n.body.last().map_or(false, |n| self.decreasing(n))
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::cols;
+    use types::{CallInfo, TypeArguments, TypeRef};
+
+    fn ident(name: &str) -> hir::Identifier {
+        hir::Identifier { name: name.to_string(), location: cols(1, 1) }
+    }
+
+    fn int(value: i64) -> hir::Expression {
+        hir::Expression::Int(Box::new(hir::IntLiteral {
+            value,
+            resolved_type: TypeRef::Unknown,
+            location: cols(1, 1),
+        }))
+    }
+
+    fn var(id: VariableId) -> hir::Expression {
+        hir::Expression::IdentifierRef(Box::new(hir::IdentifierRef {
+            name: "n".to_string(),
+            kind: IdentifierKind::Variable(id),
+            location: cols(1, 1),
+        }))
+    }
+
+    fn call(
+        kind: CallKind,
+        name: &str,
+        receiver: Option<hir::Expression>,
+        arguments: Vec<hir::Expression>,
+    ) -> hir::Expression {
+        hir::Expression::Call(Box::new(hir::Call {
+            kind,
+            receiver,
+            name: ident(name),
+            arguments: arguments
+                .into_iter()
+                .map(|value| {
+                    hir::Argument::Positional(Box::new(
+                        hir::PositionalArgument {
+                            value,
+                            expected_type: TypeRef::Unknown,
+                        },
+                    ))
+                })
+                .collect(),
+            location: cols(2, 2),
+        }))
+    }
+
+    fn recurse(
+        method: MethodId,
+        receiver: Option<hir::Expression>,
+        arguments: Vec<hir::Expression>,
+    ) -> hir::Expression {
+        let info = CallInfo {
+            id: method,
+            receiver: Receiver::Implicit,
+            returns: TypeRef::Unknown,
+            dynamic: false,
+            type_arguments: TypeArguments::new(),
+        };
+
+        call(CallKind::Call(info), "foo", receiver, arguments)
+    }
+
+    fn sub(left: hir::Expression, right: hir::Expression) -> hir::Expression {
+        call(CallKind::Unknown, "-", Some(left), vec![right])
+    }
+
+    fn check(
+        method: MethodId,
+        body: &[hir::Expression],
+    ) -> Vec<SourceLocation> {
+        let mut calls = Calls::new(method);
+
+        calls.expressions(body, true);
+        calls.locations
+    }
+
+    #[test]
+    fn test_non_tail_recursion() {
+        let method = MethodId(0);
+        let body =
+            vec![recurse(method, None, vec![var(VariableId(0))]), int(0)];
+
+        assert_eq!(check(method, &body), vec![cols(2, 2)]);
+    }
+
+    #[test]
+    fn test_tail_recursion() {
+        let method = MethodId(0);
+        let body = vec![recurse(method, None, vec![var(VariableId(0))])];
+
+        assert!(check(method, &body).is_empty());
+    }
+
+    #[test]
+    fn test_recursion_with_decreasing_argument() {
+        let method = MethodId(0);
+        let arg = sub(var(VariableId(0)), int(1));
+        let body = vec![recurse(method, None, vec![arg]), int(0)];
+
+        assert!(check(method, &body).is_empty());
+    }
+
+    #[test]
+    fn test_recursion_with_increasing_argument() {
+        let method = MethodId(0);
+
+        for op in ["+", "*"] {
+            let arg = call(
+                CallKind::Unknown,
+                op,
+                Some(var(VariableId(0))),
+                vec![int(1)],
+            );
+            let body = vec![recurse(method, None, vec![arg]), int(0)];
+
+            assert_eq!(check(method, &body), vec![cols(2, 2)]);
+        }
+    }
+
+    #[test]
+    fn test_recursion_with_decreasing_variable() {
+        let method = MethodId(0);
+        let define =
+            hir::Expression::DefineVariable(Box::new(hir::DefineVariable {
+                resolved_type: TypeRef::Unknown,
+                variable_id: Some(VariableId(1)),
+                mutable: false,
+                name: ident("m"),
+                value_type: None,
+                value: sub(var(VariableId(0)), int(1)),
+                location: cols(1, 1),
+            }));
+        let body = vec![
+            define,
+            recurse(method, None, vec![var(VariableId(1))]),
+            int(0),
+        ];
+
+        assert!(check(method, &body).is_empty());
+    }
+
+    #[test]
+    fn test_recursion_with_different_receiver() {
+        let method = MethodId(0);
+        let body =
+            vec![recurse(method, Some(var(VariableId(0))), Vec::new()), int(0)];
+
+        assert!(check(method, &body).is_empty());
+    }
+
+    #[test]
+    fn test_calling_other_methods() {
+        let body = vec![recurse(MethodId(1), None, Vec::new()), int(0)];
+
+        assert!(check(MethodId(0), &body).is_empty());
+    }
+}
@@ -90,9 +90,19 @@ pub(crate) fn run(arguments: &[String]) -> Result<i32, Error> {
         "",
         "opt",
         "The amount of optimisations to apply",
-        "none,balanced,aggressive",
+        "none,balanced,aggressive,size",
+    );
+    options.optflag(
+        "",
+        "keep-assertions",
+        "Enable assertions, regardless of the optimisation level",
     );
     options.optflag("", "static", "Statically link imported C libraries");
+    options.optflag(
+        "",
+        "strip",
+        "Strip symbols and debug information from the executable",
+    );
     options.optflag("", "dot", "Output the MIR of every module as DOT files");
     options.optflag("", "verify-llvm", "Verify LLVM IR when generating code");
     options.optflag("", "write-llvm", "Write LLVM IR files to disk");
@@ -153,6 +163,10 @@ pub(crate) fn run(arguments: &[String]) -> Result<i32, Error> {
         config.set_opt(&val)?;
     }
 
+    if matches.opt_present("keep-assertions") {
+        config.keep_assertions = true;
+    }
+
     if matches.opt_present("dot") {
         config.dot = true;
     }
@@ -169,6 +183,10 @@ pub(crate) fn run(arguments: &[String]) -> Result<i32, Error> {
         config.static_linking = true;
     }
 
+    if matches.opt_present("strip") {
+        config.strip = true;
+    }
+
     for path in matches.opt_strs("i") {
         config.add_source_directory(path.into());
     }
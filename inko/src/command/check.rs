@@ -3,6 +3,8 @@ use crate::options::print_usage;
 use compiler::compiler::{CompileError, Compiler};
 use compiler::config::Config as CompilerConfig;
 use getopts::Options;
+use std::env::current_dir;
+use std::fs::{read_to_string, write};
 use std::path::PathBuf;
 
 const USAGE: &str = "Usage: inko check [OPTIONS] [FILE]
@@ -12,7 +14,13 @@ Check an entire project or a file for errors.
 Examples:
 
     inko check                   # Check all project files
-    inko check hello.inko        # Check the file hello.inko";
+    inko check hello.inko        # Check the file hello.inko
+    inko check --lint-recursion  # Also warn about unbounded recursion
+
+Baselines:
+
+    inko check --write-baseline warnings.txt  # Save the current warnings
+    inko check --baseline warnings.txt        # Fail on any new warnings";
 
 /// Type-checks Inko source code.
 pub(crate) fn run(arguments: &[String]) -> Result<i32, Error> {
@@ -33,6 +41,26 @@ pub(crate) fn run(arguments: &[String]) -> Result<i32, Error> {
         "PATH",
     );
 
+    options.optflag(
+        "",
+        "lint-recursion",
+        "Warn about recursion that may exhaust the stack",
+    );
+
+    options.optopt(
+        "",
+        "baseline",
+        "Ignore the warnings in a baseline, and fail on any others",
+        "PATH",
+    );
+
+    options.optopt(
+        "",
+        "write-baseline",
+        "Write the warnings produced to a baseline",
+        "PATH",
+    );
+
     let matches = options.parse(arguments)?;
 
     if matches.opt_present("h") {
@@ -50,6 +78,10 @@ pub(crate) fn run(arguments: &[String]) -> Result<i32, Error> {
         config.add_source_directory(path.into());
     }
 
+    if matches.opt_present("lint-recursion") {
+        config.lint_recursion = true;
+    }
+
     if config.tests.is_dir() {
         config.add_source_directory(config.tests.clone());
     }
@@ -57,10 +89,27 @@ pub(crate) fn run(arguments: &[String]) -> Result<i32, Error> {
     let mut compiler = Compiler::new(config);
     let file = matches.free.first().map(PathBuf::from);
     let result = compiler.check(file);
+    let root = current_dir().unwrap_or_else(|_| PathBuf::new());
+    let mut new_warnings = false;
+
+    if let Some(path) = matches.opt_str("write-baseline") {
+        write(&path, compiler.warnings_baseline(&root)).map_err(|err| {
+            Error::from(format!("Failed to write {}: {}", path, err))
+        })?;
+    }
+
+    if let Some(path) = matches.opt_str("baseline") {
+        let baseline = read_to_string(&path).map_err(|err| {
+            Error::from(format!("Failed to read {}: {}", path, err))
+        })?;
+
+        new_warnings = compiler.apply_baseline(&root, &baseline);
+    }
 
     compiler.print_diagnostics();
 
     match result {
+        Ok(_) if new_warnings => Ok(1),
         Ok(_) => Ok(0),
         Err(CompileError::Invalid) => Ok(1),
         Err(CompileError::Internal(msg)) => Err(Error::from(msg)),
@@ -1,7 +1,7 @@
 use crate::error::Error;
 use crate::options::print_usage;
 use crate::pkg::git::Repository;
-use crate::pkg::util::data_dir;
+use crate::pkg::util::{data_dir, lock_cache, lock_project};
 use compiler::pkg::manifest::{Checksum, Manifest, Url, MANIFEST_FILE};
 use compiler::pkg::version::Version;
 use getopts::Options;
@@ -46,6 +46,8 @@ pub(crate) fn run(args: &[String]) -> Result<i32, Error> {
             || Error::from("The package version is invalid".to_string()),
         )?;
 
+    let _project = lock_project()?;
+    let _cache = lock_cache()?;
     let dir = data_dir()?.join(url.directory_name());
     let (mut repo, fetch) = if dir.is_dir() {
         (Repository::open(&dir)?, true)
@@ -1,5 +1,6 @@
 use crate::error::Error;
 use crate::options::print_usage;
+use crate::pkg::util::lock_project;
 use compiler::pkg::manifest::{Manifest, Url, MANIFEST_FILE};
 use getopts::Options;
 
@@ -33,6 +34,7 @@ pub(crate) fn run(args: &[String]) -> Result<i32, Error> {
         .and_then(|uri| Url::parse(uri))
         .ok_or_else(|| "The package URL is invalid".to_string())?;
 
+    let _project = lock_project()?;
     let mut manifest = Manifest::load(&MANIFEST_FILE)?;
 
     manifest.remove_dependency(&url);
@@ -1,7 +1,7 @@
 use crate::error::Error;
 use crate::options::print_usage;
 use crate::pkg::git::Repository;
-use crate::pkg::util::{cp_r, data_dir};
+use crate::pkg::util::{cp_r, data_dir, lock_cache, lock_project};
 use compiler::config::Config;
 use compiler::pkg::manifest::{Dependency, Manifest, Url, MANIFEST_FILE};
 use compiler::pkg::version::{select, Version};
@@ -41,6 +41,8 @@ pub(crate) fn run(args: &[String]) -> Result<i32, Error> {
         return Ok(0);
     }
 
+    let _project = lock_project()?;
+    let _cache = lock_cache()?;
     let config = Config::default();
     let packages = download_packages()?;
     let versions = select(packages.iter().map(|p| &p.dependency));
@@ -52,7 +54,12 @@ pub(crate) fn run(args: &[String]) -> Result<i32, Error> {
 
 fn download_packages() -> Result<Vec<Package>, Error> {
     let data_dir = data_dir()?;
-    let mut manifests = vec![Manifest::load(&MANIFEST_FILE)?];
+    let inko = Version::inko();
+    let root = Manifest::load(&MANIFEST_FILE)?;
+
+    root.check_inko_version("This project", &inko)?;
+
+    let mut manifests = vec![root];
     let mut packages = Vec::new();
     let mut downloaded = HashSet::new();
 
@@ -68,6 +75,13 @@ fn download_packages() -> Result<Vec<Package>, Error> {
 
             match download_dependency(&data_dir, dep)? {
                 (package, Some(manifest)) => {
+                    manifest.check_inko_version(
+                        &format!(
+                            "{} v{}",
+                            package.dependency.url, package.dependency.version
+                        ),
+                        &inko,
+                    )?;
                     manifests.push(manifest);
                     packages.push(package);
                 }
@@ -1,7 +1,7 @@
 use crate::error::Error;
 use crate::options::print_usage;
 use crate::pkg::git::Repository;
-use crate::pkg::util::data_dir;
+use crate::pkg::util::{data_dir, lock_cache, lock_project};
 use compiler::pkg::manifest::{
     Checksum, Dependency, Manifest, Url, MANIFEST_FILE,
 };
@@ -36,6 +36,8 @@ pub(crate) fn run(args: &[String]) -> Result<i32, Error> {
     }
 
     let major = matches.opt_present("m");
+    let _project = lock_project()?;
+    let _cache = lock_cache()?;
     let mut manifest = Manifest::load(&MANIFEST_FILE)?;
     let update = if let Some(url) =
         matches.free.first().and_then(|uri| Url::parse(uri))
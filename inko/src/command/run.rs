@@ -1,5 +1,7 @@
 use crate::error::Error;
-use crate::options::print_usage;
+use crate::options::{
+    define_runtime_options, print_usage, runtime_environment,
+};
 use compiler::compiler::{CompileError, Compiler};
 use compiler::config::Config;
 use getopts::{Options, ParsingStyle};
@@ -23,7 +25,8 @@ Arguments passed _after_ the file to run are passed to the resulting executable.
 Examples:
 
     inko run hello.inko        # Compile and run the file hello.inko
-    inko run hello.inko --foo  # Passes --foo to the resulting executable";
+    inko run hello.inko --foo  # Passes --foo to the resulting executable
+    inko run --threads 2 hello.inko  # Run using two process threads";
 
 pub(crate) fn run(arguments: &[String]) -> Result<i32, Error> {
     let mut options = Options::new();
@@ -45,6 +48,7 @@ pub(crate) fn run(arguments: &[String]) -> Result<i32, Error> {
     );
 
     options.optflag("", "static", "Statically link imported C libraries");
+    define_runtime_options(&mut options);
 
     let matches = options.parse(arguments)?;
 
@@ -53,6 +57,7 @@ pub(crate) fn run(arguments: &[String]) -> Result<i32, Error> {
         return Ok(0);
     }
 
+    let env = runtime_environment(&matches)?;
     let mut config = Config::default();
     let arguments =
         if matches.free.len() > 1 { &matches.free[1..] } else { &[] };
@@ -97,6 +102,7 @@ pub(crate) fn run(arguments: &[String]) -> Result<i32, Error> {
         Ok(exe) => {
             let status = Command::new(exe)
                 .args(arguments)
+                .envs(env)
                 .spawn()
                 .and_then(|mut child| child.wait())
                 .map_err(|err| {
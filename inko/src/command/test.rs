@@ -1,5 +1,7 @@
 use crate::error::Error;
-use crate::options::print_usage;
+use crate::options::{
+    define_runtime_options, print_usage, runtime_environment,
+};
 use compiler::compiler::{CompileError, Compiler};
 use compiler::config::{Config, Output, SOURCE_EXT};
 use getopts::Options;
@@ -13,17 +15,27 @@ const USAGE: &str = "Usage: inko test [OPTIONS]
 Compiles and runs unit tests
 
 This command compiles your unit tests in ./test, then runs the resulting test
-executable.
+executable. Additional directories to find tests in can be specified using the
+--directory option.
 
 Examples:
 
-    inko test    # Runs all unit tests in ./test";
+    inko test                  # Runs all unit tests in ./test
+    inko test --threads 1      # Runs the tests using a single process thread
+    inko test --directory src  # Also runs the unit tests in ./src";
 
 /// Compiles and runs Inko unit tests.
 pub(crate) fn run(arguments: &[String]) -> Result<i32, Error> {
     let mut options = Options::new();
 
     options.optflag("h", "help", "Show this help message");
+    options.optmulti(
+        "",
+        "directory",
+        "An additional directory to find unit tests in",
+        "PATH",
+    );
+    define_runtime_options(&mut options);
 
     let matches = options.parse(arguments)?;
 
@@ -32,20 +44,35 @@ pub(crate) fn run(arguments: &[String]) -> Result<i32, Error> {
         return Ok(0);
     }
 
+    let env = runtime_environment(&matches)?;
     let mut config = Config::default();
     let input = config.main_test_module();
+    let extra = matches.opt_strs("directory");
+    let mut roots = Vec::new();
+
+    // The default tests directory is optional when additional directories are
+    // specified, so projects that only have tests next to their source files
+    // don't need an empty ./test directory.
+    if extra.is_empty() || config.tests.is_dir() {
+        roots.push(config.tests.clone());
+    }
+
+    roots.extend(extra.into_iter().map(PathBuf::from));
+
+    for root in &roots {
+        if !root.is_dir() {
+            return Err(Error::from(format!(
+                "The tests directory {:?} doesn't exist",
+                root
+            )));
+        }
 
-    if !config.tests.is_dir() {
-        return Err(Error::from(format!(
-            "The tests directory {:?} doesn't exist",
-            config.tests
-        )));
+        config.add_source_directory(root.clone());
     }
 
-    config.add_source_directory(config.tests.clone());
     config.output = Output::File("inko-tests".to_string());
 
-    let tests = test_module_names(&config.tests).map_err(|err| {
+    let tests = test_module_names(&roots).map_err(|err| {
         Error::from(format!("Failed to find test modules: {}", err))
     })?;
 
@@ -55,6 +82,10 @@ pub(crate) fn run(arguments: &[String]) -> Result<i32, Error> {
     // the generated file in it (if it doesn't already exist that is).
     compiler.create_build_directory()?;
 
+    // The lock is acquired before writing the test module, as concurrent runs
+    // would otherwise overwrite it.
+    compiler.lock_build_directory()?;
+
     write(&input, generate_main_test_module(tests)).map_err(|err| {
         Error::from(format!("Failed to write {}: {}", input.display(), err))
     })?;
@@ -66,6 +97,7 @@ pub(crate) fn run(arguments: &[String]) -> Result<i32, Error> {
     match result {
         Ok(exe) => Command::new(exe)
             .args(matches.free)
+            .envs(env)
             .spawn()
             .and_then(|mut child| child.wait())
             .map_err(|err| {
@@ -114,14 +146,24 @@ fn test_files(test_dir: &Path) -> Result<Vec<PathBuf>, std::io::Error> {
 }
 
 fn test_module_names(
-    test_dir: &Path,
+    roots: &[PathBuf],
 ) -> Result<Vec<ModuleName>, std::io::Error> {
-    let test_modules = test_files(test_dir)?
-        .into_iter()
-        .map(|file| {
-            ModuleName::from_relative_path(file.strip_prefix(test_dir).unwrap())
-        })
-        .collect::<Vec<_>>();
+    let mut test_modules = Vec::new();
+
+    for root in roots {
+        for file in test_files(root)? {
+            let name = ModuleName::from_relative_path(
+                file.strip_prefix(root).unwrap(),
+            );
+
+            // The same module may be found in multiple directories, in which
+            // case only the first one is used, as that's also the one the
+            // compiler picks when importing the module.
+            if !test_modules.contains(&name) {
+                test_modules.push(name);
+            }
+        }
+    }
 
     Ok(test_modules)
 }
@@ -1,5 +1,39 @@
 //! Generic helper functions that don't belong to any particular module.
-use getopts::Options;
+use crate::error::Error;
+use getopts::{Matches, Options};
+
+/// The runtime settings that can be set using command-line options, along with
+/// the environment variables used to pass them to the executable, and the
+/// maximum value supported by the runtime.
+///
+/// The runtime ignores values that exceed these limits, so these must be kept
+/// in sync with the types used in rt/src/config.rs.
+const RUNTIME_OPTIONS: [(&str, &str, &str, u32); 4] = [
+    (
+        "threads",
+        "INKO_PROCESS_THREADS",
+        "The number of threads to use for running processes",
+        u16::MAX as u32,
+    ),
+    (
+        "backup-threads",
+        "INKO_BACKUP_THREADS",
+        "The number of threads to use for replacing blocked threads",
+        u16::MAX as u32,
+    ),
+    (
+        "netpoll-threads",
+        "INKO_NETPOLL_THREADS",
+        "The number of threads to use for polling sockets",
+        127,
+    ),
+    (
+        "stack-size",
+        "INKO_STACK_SIZE",
+        "The size of each process' stack in bytes",
+        u32::MAX,
+    ),
+];
 
 /// Prints a usage message for a set of CLI options.
 pub(crate) fn print_usage(options: &Options, brief: &str) {
@@ -13,3 +47,34 @@ pub(crate) fn print_usage(options: &Options, brief: &str) {
 
     println!("{}", out);
 }
+
+/// Defines the options used for configuring the runtime of an executable.
+pub(crate) fn define_runtime_options(options: &mut Options) {
+    for (name, _, description, _) in RUNTIME_OPTIONS {
+        options.optopt("", name, description, "NUM");
+    }
+}
+
+/// Returns the environment variables to set for an executable, based on the
+/// runtime options specified.
+pub(crate) fn runtime_environment(
+    matches: &Matches,
+) -> Result<Vec<(&'static str, String)>, Error> {
+    let mut vars = Vec::new();
+
+    for (name, var, _, max) in RUNTIME_OPTIONS {
+        if let Some(val) = matches.opt_str(name) {
+            match val.parse::<u32>() {
+                Ok(num) if num > 0 && num <= max => vars.push((var, val)),
+                _ => {
+                    return Err(Error::from(format!(
+                        "The value of --{} must be an integer between 1 and {}",
+                        name, max
+                    )));
+                }
+            }
+        }
+    }
+
+    Ok(vars)
+}
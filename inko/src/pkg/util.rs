@@ -1,13 +1,34 @@
-use compiler::config;
+use compiler::config::{self, Config};
+use compiler::lock::Lock;
 use std::fs::{copy, create_dir_all, read_dir};
 use std::path::{Path, PathBuf};
 
+/// The name of the file used to lock the package cache.
+const CACHE_LOCK: &str = ".lock";
+
+/// The name of the file (in the build directory) used to lock the manifest and
+/// dependencies of a project.
+const PROJECT_LOCK: &str = "pkg.lock";
+
 pub(crate) fn data_dir() -> Result<PathBuf, String> {
     config::data_directory()
         .map(|p| p.join("packages"))
         .ok_or_else(|| "No data directory could be determined".to_string())
 }
 
+/// Locks the package cache shared by all projects.
+///
+/// When also locking the project using `lock_project()`, the project must be
+/// locked first.
+pub(crate) fn lock_cache() -> Result<Lock, String> {
+    Lock::acquire(&data_dir()?.join(CACHE_LOCK))
+}
+
+/// Locks the manifest and dependencies of the current project.
+pub(crate) fn lock_project() -> Result<Lock, String> {
+    Lock::acquire(&Config::default().build.join(PROJECT_LOCK))
+}
+
 pub(crate) fn cp_r(source: &Path, target: &Path) -> Result<(), String> {
     create_dir_all(target).map_err(|e| e.to_string())?;
 
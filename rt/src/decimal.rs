@@ -0,0 +1,737 @@
+//! Arbitrary precision decimal numbers.
+//!
+//! A decimal is stored as an unsigned integer of arbitrary size, a sign, and a
+//! scale: the number of digits after the decimal point. The value `12.34` is
+//! thus stored as the integer `1234` with a scale of 2.
+use std::cmp::Ordering;
+
+/// The base of each limb when converting to and from decimal strings.
+const DECIMAL_BASE: u32 = 1_000_000_000;
+
+/// The number of decimal digits in each `DECIMAL_BASE` chunk.
+const DECIMAL_DIGITS: usize = 9;
+
+/// The maximum scale of a decimal.
+///
+/// Changing the scale of a decimal requires multiplying or dividing it by a
+/// power of ten, the cost of which grows with the scale. Bounding the scale
+/// ensures operations on decimals can't take an unreasonable amount of time or
+/// memory.
+pub const MAX_SCALE: u32 = 10_000;
+
+/// An unsigned integer of arbitrary size.
+///
+/// The integer is stored as a list of 32 bits limbs in little-endian order.
+/// The list never contains trailing zero limbs, so zero is an empty list.
+#[derive(Clone, Eq, PartialEq, Debug)]
+struct BigUint {
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    fn zero() -> BigUint {
+        BigUint { limbs: Vec::new() }
+    }
+
+    fn from_u64(value: u64) -> BigUint {
+        let mut int =
+            BigUint { limbs: vec![value as u32, (value >> 32) as u32] };
+
+        int.normalize();
+        int
+    }
+
+    fn pow10(exponent: u32) -> BigUint {
+        let mut int = BigUint::from_u64(1);
+
+        for _ in 0..exponent {
+            int.mul_small(10);
+        }
+
+        int
+    }
+
+    fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    fn is_odd(&self) -> bool {
+        self.limbs.first().map_or(false, |v| v & 1 == 1)
+    }
+
+    fn normalize(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+
+    fn bits(&self) -> usize {
+        match self.limbs.last() {
+            Some(last) => self.limbs.len() * 32 - last.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    fn bit(&self, index: usize) -> bool {
+        (self.limbs[index / 32] >> (index % 32)) & 1 == 1
+    }
+
+    fn mul_small(&mut self, value: u32) {
+        let mut carry = 0_u64;
+
+        for limb in &mut self.limbs {
+            let res = (*limb as u64) * (value as u64) + carry;
+
+            *limb = res as u32;
+            carry = res >> 32;
+        }
+
+        if carry > 0 {
+            self.limbs.push(carry as u32);
+        }
+
+        self.normalize();
+    }
+
+    fn add_small(&mut self, value: u32) {
+        let mut carry = value as u64;
+
+        for limb in &mut self.limbs {
+            if carry == 0 {
+                break;
+            }
+
+            let res = *limb as u64 + carry;
+
+            *limb = res as u32;
+            carry = res >> 32;
+        }
+
+        if carry > 0 {
+            self.limbs.push(carry as u32);
+        }
+    }
+
+    /// Divides `self` by `value` in place, returning the remainder.
+    fn div_small(&mut self, value: u32) -> u32 {
+        let mut rem = 0_u64;
+
+        for limb in self.limbs.iter_mut().rev() {
+            let cur = (rem << 32) | *limb as u64;
+
+            *limb = (cur / value as u64) as u32;
+            rem = cur % value as u64;
+        }
+
+        self.normalize();
+        rem as u32
+    }
+
+    fn add(&self, other: &BigUint) -> BigUint {
+        let len = self.limbs.len().max(other.limbs.len());
+        let mut limbs = Vec::with_capacity(len + 1);
+        let mut carry = 0_u64;
+
+        for i in 0..len {
+            let a = *self.limbs.get(i).unwrap_or(&0) as u64;
+            let b = *other.limbs.get(i).unwrap_or(&0) as u64;
+            let res = a + b + carry;
+
+            limbs.push(res as u32);
+            carry = res >> 32;
+        }
+
+        if carry > 0 {
+            limbs.push(carry as u32);
+        }
+
+        BigUint { limbs }
+    }
+
+    /// Subtracts `other` from `self`, which must be greater than or equal to
+    /// `other`.
+    fn sub(&self, other: &BigUint) -> BigUint {
+        let mut limbs = Vec::with_capacity(self.limbs.len());
+        let mut borrow = 0_i64;
+
+        for (i, &limb) in self.limbs.iter().enumerate() {
+            let mut res =
+                limb as i64 - *other.limbs.get(i).unwrap_or(&0) as i64 - borrow;
+
+            if res < 0 {
+                res += 1 << 32;
+                borrow = 1;
+            } else {
+                borrow = 0;
+            }
+
+            limbs.push(res as u32);
+        }
+
+        let mut int = BigUint { limbs };
+
+        int.normalize();
+        int
+    }
+
+    fn mul(&self, other: &BigUint) -> BigUint {
+        if self.is_zero() || other.is_zero() {
+            return BigUint::zero();
+        }
+
+        let mut limbs = vec![0_u32; self.limbs.len() + other.limbs.len()];
+
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0_u64;
+
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let res = limbs[i + j] as u64 + (a as u64) * (b as u64) + carry;
+
+                limbs[i + j] = res as u32;
+                carry = res >> 32;
+            }
+
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+
+        let mut int = BigUint { limbs };
+
+        int.normalize();
+        int
+    }
+
+    fn shift_left_one(&mut self) {
+        let mut carry = 0;
+
+        for limb in &mut self.limbs {
+            let next = *limb >> 31;
+
+            *limb = (*limb << 1) | carry;
+            carry = next;
+        }
+
+        if carry > 0 {
+            self.limbs.push(carry);
+        }
+    }
+
+    /// Divides `self` by `other`, returning the quotient and remainder.
+    ///
+    /// The divisor must not be zero.
+    fn div_rem(&self, other: &BigUint) -> (BigUint, BigUint) {
+        if other.limbs.len() == 1 {
+            let mut quotient = self.clone();
+            let rem = quotient.div_small(other.limbs[0]);
+
+            return (quotient, BigUint::from_u64(rem as u64));
+        }
+
+        if self.cmp(other) == Ordering::Less {
+            return (BigUint::zero(), self.clone());
+        }
+
+        // A simple shift-subtract division. Decimals used in practise are
+        // rarely more than a few limbs in size, so this is fast enough.
+        let bits = self.bits();
+        let mut quotient = BigUint { limbs: vec![0; self.limbs.len()] };
+        let mut rem = BigUint::zero();
+
+        for index in (0..bits).rev() {
+            rem.shift_left_one();
+
+            if self.bit(index) {
+                rem.add_small(1);
+            }
+
+            if rem.cmp(other) != Ordering::Less {
+                rem = rem.sub(other);
+                quotient.limbs[index / 32] |= 1 << (index % 32);
+            }
+        }
+
+        quotient.normalize();
+        (quotient, rem)
+    }
+
+    fn cmp(&self, other: &BigUint) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+
+    fn to_decimal_string(&self) -> String {
+        if self.is_zero() {
+            return "0".to_string();
+        }
+
+        let mut chunks = Vec::new();
+        let mut int = self.clone();
+
+        while !int.is_zero() {
+            chunks.push(int.div_small(DECIMAL_BASE));
+        }
+
+        let mut result = chunks.pop().unwrap().to_string();
+
+        for chunk in chunks.iter().rev() {
+            result.push_str(&format!(
+                "{:0width$}",
+                chunk,
+                width = DECIMAL_DIGITS
+            ));
+        }
+
+        result
+    }
+}
+
+/// The rounding mode to use when reducing the scale of a decimal.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Rounding {
+    /// Round towards zero.
+    Down,
+
+    /// Round away from zero.
+    Up,
+
+    /// Round towards negative infinity.
+    Floor,
+
+    /// Round towards positive infinity.
+    Ceiling,
+
+    /// Round to the nearest value, rounding ties away from zero.
+    HalfUp,
+
+    /// Round to the nearest value, rounding ties towards zero.
+    HalfDown,
+
+    /// Round to the nearest value, rounding ties to the nearest even value.
+    HalfEven,
+}
+
+impl Rounding {
+    pub fn from_int(value: i64) -> Rounding {
+        match value {
+            0 => Rounding::Down,
+            1 => Rounding::Up,
+            2 => Rounding::Floor,
+            3 => Rounding::Ceiling,
+            4 => Rounding::HalfUp,
+            5 => Rounding::HalfDown,
+            _ => Rounding::HalfEven,
+        }
+    }
+
+    /// Returns `true` if the magnitude of a truncated quotient should be
+    /// incremented by one, based on the remainder of the division.
+    fn increment(
+        self,
+        quotient: &BigUint,
+        remainder: &BigUint,
+        divisor: &BigUint,
+        negative: bool,
+    ) -> bool {
+        if remainder.is_zero() {
+            return false;
+        }
+
+        let mut twice = remainder.clone();
+
+        twice.mul_small(2);
+
+        let half = twice.cmp(divisor);
+
+        match self {
+            Rounding::Down => false,
+            Rounding::Up => true,
+            Rounding::Floor => negative,
+            Rounding::Ceiling => !negative,
+            Rounding::HalfUp => half != Ordering::Less,
+            Rounding::HalfDown => half == Ordering::Greater,
+            Rounding::HalfEven => {
+                half == Ordering::Greater
+                    || (half == Ordering::Equal && quotient.is_odd())
+            }
+        }
+    }
+}
+
+/// A decimal number of arbitrary precision.
+#[derive(Clone, Debug)]
+pub struct Decimal {
+    negative: bool,
+    value: BigUint,
+    scale: u32,
+}
+
+impl Decimal {
+    pub fn from_int(value: i64) -> Decimal {
+        Decimal {
+            negative: value < 0,
+            value: BigUint::from_u64(value.unsigned_abs()),
+            scale: 0,
+        }
+    }
+
+    /// Parses a decimal from a string, such as `-12.34`.
+    ///
+    /// The input may start with a sign (`-` or `+`), followed by one or more
+    /// digits, optionally followed by a `.` and one or more digits.
+    ///
+    /// If the input is invalid or has more than `MAX_SCALE` digits after the
+    /// `.`, a `None` is returned.
+    pub fn parse(input: &str) -> Option<Decimal> {
+        let (negative, rest) = match input.as_bytes().first() {
+            Some(b'-') => (true, &input[1..]),
+            Some(b'+') => (false, &input[1..]),
+            _ => (false, input),
+        };
+
+        let (int, frac) = match rest.split_once('.') {
+            Some((int, frac)) if !frac.is_empty() => (int, frac),
+            Some(_) => return None,
+            None => (rest, ""),
+        };
+
+        if int.is_empty() || frac.len() > MAX_SCALE as usize {
+            return None;
+        }
+
+        let mut value = BigUint::zero();
+
+        for byte in int.bytes().chain(frac.bytes()) {
+            if !byte.is_ascii_digit() {
+                return None;
+            }
+
+            value.mul_small(10);
+            value.add_small((byte - b'0') as u32);
+        }
+
+        Some(Decimal { negative, value, scale: frac.len() as u32 })
+    }
+
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.value.is_zero()
+    }
+
+    pub fn add(&self, other: &Decimal) -> Decimal {
+        let (a, b, scale) = Self::align(self, other);
+
+        if self.negative == other.negative {
+            return Decimal {
+                negative: self.negative,
+                value: a.add(&b),
+                scale,
+            };
+        }
+
+        match a.cmp(&b) {
+            Ordering::Less => {
+                Decimal { negative: other.negative, value: b.sub(&a), scale }
+            }
+            _ => Decimal { negative: self.negative, value: a.sub(&b), scale },
+        }
+    }
+
+    pub fn sub(&self, other: &Decimal) -> Decimal {
+        self.add(&other.negate())
+    }
+
+    /// Multiplies `self` by `other`, producing a decimal with the sum of both
+    /// scales.
+    ///
+    /// If the resulting scale is greater than `MAX_SCALE`, a `None` is
+    /// returned.
+    pub fn mul(&self, other: &Decimal) -> Option<Decimal> {
+        let scale =
+            self.scale.checked_add(other.scale).filter(|&v| v <= MAX_SCALE)?;
+
+        Some(Decimal {
+            negative: self.negative != other.negative,
+            value: self.value.mul(&other.value),
+            scale,
+        })
+    }
+
+    /// Divides `self` by `other`, producing a decimal with the given scale.
+    ///
+    /// If `other` is zero or `scale` is greater than `MAX_SCALE`, a `None` is
+    /// returned.
+    pub fn div(
+        &self,
+        other: &Decimal,
+        scale: u32,
+        rounding: Rounding,
+    ) -> Option<Decimal> {
+        if other.is_zero() || scale > MAX_SCALE {
+            return None;
+        }
+
+        // (a / 10^sa) / (b / 10^sb) = (a * 10^(scale + sb)) / (b * 10^sa),
+        // producing a quotient with `scale` digits after the decimal point.
+        let num = self.value.mul(&BigUint::pow10(scale + other.scale));
+        let div = other.value.mul(&BigUint::pow10(self.scale));
+        let negative = self.negative != other.negative;
+
+        Some(Self::divide(negative, &num, &div, scale, rounding))
+    }
+
+    /// Returns a copy of `self` with the given scale.
+    ///
+    /// If the scale is reduced, the value is rounded according to the rounding
+    /// mode. If `scale` is greater than `MAX_SCALE`, a `None` is returned.
+    pub fn round(&self, scale: u32, rounding: Rounding) -> Option<Decimal> {
+        if scale > MAX_SCALE {
+            return None;
+        }
+
+        if scale >= self.scale {
+            return Some(Decimal {
+                negative: self.negative,
+                value: self.value.mul(&BigUint::pow10(scale - self.scale)),
+                scale,
+            });
+        }
+
+        let div = BigUint::pow10(self.scale - scale);
+
+        Some(Self::divide(self.negative, &self.value, &div, scale, rounding))
+    }
+
+    pub fn negate(&self) -> Decimal {
+        Decimal {
+            negative: !self.negative,
+            value: self.value.clone(),
+            scale: self.scale,
+        }
+    }
+
+    pub fn compare(&self, other: &Decimal) -> Ordering {
+        // Zero is neither positive nor negative, so -0 and 0 are equal.
+        let a_neg = self.negative && !self.is_zero();
+        let b_neg = other.negative && !other.is_zero();
+
+        match (a_neg, b_neg) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (neg, _) => {
+                let (a, b, _) = Self::align(self, other);
+                let ord = a.cmp(&b);
+
+                if neg {
+                    ord.reverse()
+                } else {
+                    ord
+                }
+            }
+        }
+    }
+
+    fn align(a: &Decimal, b: &Decimal) -> (BigUint, BigUint, u32) {
+        match a.scale.cmp(&b.scale) {
+            Ordering::Less => (
+                a.value.mul(&BigUint::pow10(b.scale - a.scale)),
+                b.value.clone(),
+                b.scale,
+            ),
+            Ordering::Greater => (
+                a.value.clone(),
+                b.value.mul(&BigUint::pow10(a.scale - b.scale)),
+                a.scale,
+            ),
+            Ordering::Equal => (a.value.clone(), b.value.clone(), a.scale),
+        }
+    }
+
+    fn divide(
+        negative: bool,
+        num: &BigUint,
+        div: &BigUint,
+        scale: u32,
+        rounding: Rounding,
+    ) -> Decimal {
+        let (mut value, rem) = num.div_rem(div);
+
+        if rounding.increment(&value, &rem, div, negative) {
+            value.add_small(1);
+        }
+
+        Decimal { negative, value, scale }
+    }
+}
+
+impl ToString for Decimal {
+    fn to_string(&self) -> String {
+        let digits = self.value.to_decimal_string();
+        let scale = self.scale as usize;
+        let mut result = String::new();
+
+        if self.negative && !self.is_zero() {
+            result.push('-');
+        }
+
+        if scale == 0 {
+            result.push_str(&digits);
+        } else if digits.len() > scale {
+            let (int, frac) = digits.split_at(digits.len() - scale);
+
+            result.push_str(int);
+            result.push('.');
+            result.push_str(frac);
+        } else {
+            result.push_str("0.");
+            result.extend(std::iter::repeat('0').take(scale - digits.len()));
+            result.push_str(&digits);
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(input: &str) -> Decimal {
+        Decimal::parse(input).unwrap()
+    }
+
+    #[test]
+    fn test_big_uint_arithmetic() {
+        let a = BigUint::from_u64(u64::MAX);
+        let b = BigUint::from_u64(2);
+
+        assert_eq!(a.add(&b).to_decimal_string(), "18446744073709551617");
+        assert_eq!(a.sub(&b).to_decimal_string(), "18446744073709551613");
+        assert_eq!(
+            a.mul(&a).to_decimal_string(),
+            "340282366920938463426481119284349108225"
+        );
+    }
+
+    #[test]
+    fn test_big_uint_div_rem() {
+        let a = BigUint::from_u64(u64::MAX).mul(&BigUint::from_u64(1000));
+        let (q, r) = a.div_rem(&BigUint::from_u64(u64::MAX));
+
+        assert_eq!(q, BigUint::from_u64(1000));
+        assert!(r.is_zero());
+
+        let (q, r) = BigUint::from_u64(u64::MAX)
+            .div_rem(&BigUint::from_u64(10_000_000_000));
+
+        assert_eq!(q.to_decimal_string(), "1844674407");
+        assert_eq!(r.to_decimal_string(), "3709551615");
+
+        let (q, r) = BigUint::from_u64(17).div_rem(&BigUint::from_u64(5));
+
+        assert_eq!(q, BigUint::from_u64(3));
+        assert_eq!(r, BigUint::from_u64(2));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(dec("12.34").to_string(), "12.34");
+        assert_eq!(dec("-12.34").to_string(), "-12.34");
+        assert_eq!(dec("+1").to_string(), "1");
+        assert_eq!(dec("0.001").to_string(), "0.001");
+        assert_eq!(dec("-0.0").to_string(), "0.0");
+        assert_eq!(
+            dec("12345678901234567890.5").to_string(),
+            "12345678901234567890.5"
+        );
+        assert!(Decimal::parse("").is_none());
+        assert!(Decimal::parse("-").is_none());
+        assert!(Decimal::parse("1.").is_none());
+        assert!(Decimal::parse(".5").is_none());
+        assert!(Decimal::parse("1.2.3").is_none());
+        assert!(Decimal::parse("1a").is_none());
+        assert!(Decimal::parse(&format!(
+            "1.{}",
+            "0".repeat(MAX_SCALE as usize + 1)
+        ))
+        .is_none());
+    }
+
+    #[test]
+    fn test_add_sub() {
+        assert_eq!(dec("0.1").add(&dec("0.2")).to_string(), "0.3");
+        assert_eq!(dec("1.5").add(&dec("-2.25")).to_string(), "-0.75");
+        assert_eq!(dec("-1.5").add(&dec("2.25")).to_string(), "0.75");
+        assert_eq!(dec("10").sub(&dec("0.01")).to_string(), "9.99");
+        assert_eq!(dec("1.5").sub(&dec("1.5")).to_string(), "0.0");
+    }
+
+    #[test]
+    fn test_mul() {
+        let mul = |a: &str, b: &str| dec(a).mul(&dec(b)).unwrap().to_string();
+        let max = dec("1").round(MAX_SCALE, Rounding::Down).unwrap();
+
+        assert_eq!(mul("1.5", "-2.25"), "-3.375");
+        assert_eq!(mul("0.1", "0.1"), "0.01");
+        assert_eq!(max.mul(&dec("2")).unwrap().scale(), MAX_SCALE);
+        assert!(max.mul(&dec("0.1")).is_none());
+    }
+
+    #[test]
+    fn test_div() {
+        let div = |a: &str, b: &str, scale, mode| {
+            dec(a).div(&dec(b), scale, mode).unwrap().to_string()
+        };
+
+        assert_eq!(div("1", "3", 5, Rounding::HalfUp), "0.33333");
+        assert_eq!(div("2", "3", 2, Rounding::HalfUp), "0.67");
+        assert_eq!(div("2", "3", 2, Rounding::Down), "0.66");
+        assert_eq!(div("-2", "3", 2, Rounding::Floor), "-0.67");
+        assert_eq!(div("-2", "3", 2, Rounding::Ceiling), "-0.66");
+        assert_eq!(div("10.5", "0.5", 0, Rounding::HalfUp), "21");
+        assert_eq!(div("1.25", "1", 1, Rounding::HalfEven), "1.2");
+        assert_eq!(div("1.35", "1", 1, Rounding::HalfEven), "1.4");
+        assert_eq!(div("1.25", "1", 1, Rounding::HalfDown), "1.2");
+        assert!(dec("1").div(&dec("0.0"), 2, Rounding::HalfUp).is_none());
+        assert!(dec("1")
+            .div(&dec("3"), MAX_SCALE + 1, Rounding::HalfUp)
+            .is_none());
+    }
+
+    #[test]
+    fn test_round() {
+        let round = |input: &str, scale, mode| {
+            dec(input).round(scale, mode).unwrap().to_string()
+        };
+
+        assert_eq!(round("1.005", 2, Rounding::HalfUp), "1.01");
+        assert_eq!(round("-1.005", 2, Rounding::HalfUp), "-1.01");
+        assert_eq!(round("1.005", 2, Rounding::Down), "1.00");
+        assert_eq!(round("1.001", 2, Rounding::Up), "1.01");
+        assert_eq!(round("1.5", 3, Rounding::Down), "1.500");
+        assert_eq!(round("2.5", 0, Rounding::HalfEven), "2");
+        assert!(dec("1").round(MAX_SCALE + 1, Rounding::Down).is_none());
+    }
+
+    #[test]
+    fn test_compare() {
+        assert_eq!(dec("1.0").compare(&dec("1")), Ordering::Equal);
+        assert_eq!(dec("-0").compare(&dec("0.00")), Ordering::Equal);
+        assert_eq!(dec("1.01").compare(&dec("1.1")), Ordering::Less);
+        assert_eq!(dec("-1").compare(&dec("1")), Ordering::Less);
+        assert_eq!(dec("-1").compare(&dec("-2")), Ordering::Greater);
+        assert_eq!(dec("2").compare(&dec("-2")), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_from_int() {
+        assert_eq!(
+            Decimal::from_int(i64::MIN).to_string(),
+            "-9223372036854775808"
+        );
+        assert_eq!(Decimal::from_int(42).to_string(), "42");
+    }
+}
@@ -0,0 +1,460 @@
+//! Recursively walking directories.
+//!
+//! Walking directories entry by entry from Inko code is slow, as every entry
+//! requires several runtime calls. The walker in this module instead traverses
+//! the directory tree in the runtime, optionally using multiple threads, and
+//! only hands out the entries that match a glob pattern.
+use crate::glob::Pattern;
+use std::collections::{HashSet, VecDeque};
+use std::fs::{self, FileType, ReadDir};
+use std::io;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::sync::{Condvar, Mutex};
+use std::thread;
+
+/// The type of a directory entry.
+///
+/// The numeric values of these variants must match the order of the variants
+/// of the `FileType` type in the standard library.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub(crate) enum EntryType {
+    File = 0,
+    Directory = 1,
+    SymbolicLink = 2,
+    Other = 3,
+}
+
+impl EntryType {
+    fn from_file_type(kind: FileType) -> EntryType {
+        if kind.is_dir() {
+            EntryType::Directory
+        } else if kind.is_file() {
+            EntryType::File
+        } else if kind.is_symlink() {
+            EntryType::SymbolicLink
+        } else {
+            EntryType::Other
+        }
+    }
+}
+
+/// A single entry produced by a walker.
+#[derive(Eq, PartialEq, Debug)]
+pub(crate) struct Entry {
+    pub(crate) path: PathBuf,
+    pub(crate) kind: EntryType,
+}
+
+/// The unique identity of a directory, used to detect symbolic link loops.
+type DirectoryId = (u64, u64);
+
+fn directory_id(path: &Path) -> io::Result<DirectoryId> {
+    fs::metadata(path).map(|meta| (meta.dev(), meta.ino()))
+}
+
+/// Settings shared by the sequential and parallel walkers.
+struct Options {
+    root: PathBuf,
+    pattern: Option<Pattern>,
+    follow_symlinks: bool,
+}
+
+impl Options {
+    /// Processes a single directory entry.
+    ///
+    /// The return value is the entry to produce (if it matches the pattern) and
+    /// the directory to descend into (if any).
+    fn process(
+        &self,
+        entry: fs::DirEntry,
+        visited: &mut HashSet<DirectoryId>,
+    ) -> io::Result<(Option<Entry>, Option<PathBuf>)> {
+        let path = entry.path();
+        let mut kind = EntryType::from_file_type(entry.file_type()?);
+        let mut descend = kind == EntryType::Directory;
+
+        if kind == EntryType::SymbolicLink && self.follow_symlinks {
+            // Links that point to nothing are reported as links, instead of
+            // producing an error.
+            if let Ok(meta) = fs::metadata(&path) {
+                kind = EntryType::from_file_type(meta.file_type());
+                descend = kind == EntryType::Directory;
+            }
+        }
+
+        // When following symbolic links, different paths may refer to the same
+        // directory. We only visit such directories once, which also ensures
+        // we don't loop forever when a link points to one of its parents.
+        if descend && self.follow_symlinks {
+            descend = visited.insert(directory_id(&path)?);
+        }
+
+        let found =
+            self.matches(&path).then(|| Entry { path: path.clone(), kind });
+
+        Ok((found, descend.then_some(path)))
+    }
+
+    fn matches(&self, path: &Path) -> bool {
+        let pattern = if let Some(pattern) = &self.pattern {
+            pattern
+        } else {
+            return true;
+        };
+
+        path.strip_prefix(&self.root)
+            .map(|rel| pattern.matches(&rel.to_string_lossy()))
+            .unwrap_or(false)
+    }
+
+    fn visited(&self) -> io::Result<HashSet<DirectoryId>> {
+        let mut visited = HashSet::new();
+
+        if self.follow_symlinks {
+            visited.insert(directory_id(&self.root)?);
+        }
+
+        Ok(visited)
+    }
+}
+
+/// The state shared by the threads used for walking directories in parallel.
+struct SharedState {
+    pending: Vec<PathBuf>,
+    active: usize,
+    visited: HashSet<DirectoryId>,
+    found: VecDeque<io::Result<Entry>>,
+}
+
+/// A type for recursively walking a directory.
+///
+/// The root directory itself isn't included in the output. The order in which
+/// entries are produced is unspecified.
+pub struct Walker {
+    options: Options,
+
+    /// The directories that have yet to be read.
+    pending: Vec<PathBuf>,
+
+    /// The directory that is currently being read.
+    current: Option<ReadDir>,
+
+    /// The directories visited thus far, used when following symbolic links.
+    visited: HashSet<DirectoryId>,
+
+    /// Entries collected ahead of time, such as when walking in parallel.
+    buffer: VecDeque<io::Result<Entry>>,
+
+    /// The number of threads to use.
+    ///
+    /// If this value is greater than one, the entire tree is walked using this
+    /// many threads upon the first call to `Walker::next`.
+    threads: usize,
+}
+
+impl Walker {
+    pub(crate) fn new(
+        root: PathBuf,
+        pattern: Option<Pattern>,
+        follow_symlinks: bool,
+        threads: usize,
+    ) -> Walker {
+        Walker {
+            pending: vec![root.clone()],
+            options: Options { root, pattern, follow_symlinks },
+            current: None,
+            visited: HashSet::new(),
+            buffer: VecDeque::new(),
+            threads,
+        }
+    }
+
+    /// Returns the next entry, or `None` if all entries have been produced.
+    ///
+    /// Errors produced when reading a directory are returned, after which the
+    /// walker moves on to the next entry.
+    pub(crate) fn next(&mut self) -> Option<io::Result<Entry>> {
+        if self.threads > 1 {
+            let threads = self.threads;
+
+            self.threads = 1;
+            self.walk_parallel(threads);
+        } else if self.visited.is_empty() && self.options.follow_symlinks {
+            match self.options.visited() {
+                Ok(visited) => self.visited = visited,
+                Err(err) => {
+                    self.pending.clear();
+                    return Some(Err(err));
+                }
+            }
+        }
+
+        if let Some(entry) = self.buffer.pop_front() {
+            return Some(entry);
+        }
+
+        loop {
+            let current = if let Some(current) = self.current.as_mut() {
+                current
+            } else {
+                match fs::read_dir(self.pending.pop()?) {
+                    Ok(iter) => self.current.insert(iter),
+                    Err(err) => return Some(Err(err)),
+                }
+            };
+
+            let entry = match current.next() {
+                Some(Ok(entry)) => entry,
+                Some(Err(err)) => return Some(Err(err)),
+                None => {
+                    self.current = None;
+                    continue;
+                }
+            };
+
+            match self.options.process(entry, &mut self.visited) {
+                Ok((found, dir)) => {
+                    if let Some(dir) = dir {
+                        self.pending.push(dir);
+                    }
+
+                    if let Some(found) = found {
+                        return Some(Ok(found));
+                    }
+                }
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+
+    fn walk_parallel(&mut self, threads: usize) {
+        let visited = match self.options.visited() {
+            Ok(visited) => visited,
+            Err(err) => {
+                self.pending.clear();
+                self.buffer.push_back(Err(err));
+                return;
+            }
+        };
+
+        let state = Mutex::new(SharedState {
+            pending: self.pending.split_off(0),
+            active: 0,
+            visited,
+            found: VecDeque::new(),
+        });
+        let cvar = Condvar::new();
+        let options = &self.options;
+
+        thread::scope(|scope| {
+            for _ in 0..threads {
+                scope.spawn(|| Self::parallel_worker(options, &state, &cvar));
+            }
+        });
+
+        let state = state.into_inner().unwrap();
+
+        self.visited = state.visited;
+        self.buffer = state.found;
+    }
+
+    fn parallel_worker(
+        options: &Options,
+        state: &Mutex<SharedState>,
+        cvar: &Condvar,
+    ) {
+        loop {
+            let dir = {
+                let mut shared = state.lock().unwrap();
+
+                loop {
+                    if let Some(dir) = shared.pending.pop() {
+                        shared.active += 1;
+                        break dir;
+                    }
+
+                    // If no thread is reading a directory, no new directories
+                    // can be produced, meaning we're done.
+                    if shared.active == 0 {
+                        cvar.notify_all();
+                        return;
+                    }
+
+                    shared = cvar.wait(shared).unwrap();
+                }
+            };
+
+            let mut found = Vec::new();
+            let mut dirs = Vec::new();
+            let entries = match fs::read_dir(dir) {
+                Ok(iter) => iter.collect::<Vec<_>>(),
+                Err(err) => {
+                    found.push(Err(err));
+                    Vec::new()
+                }
+            };
+
+            for entry in entries {
+                let entry = match entry {
+                    Ok(entry) => entry,
+                    Err(err) => {
+                        found.push(Err(err));
+                        continue;
+                    }
+                };
+
+                // The visited set is only needed when following symbolic
+                // links, so we only acquire the lock in that case.
+                let res = if options.follow_symlinks {
+                    options.process(entry, &mut state.lock().unwrap().visited)
+                } else {
+                    options.process(entry, &mut HashSet::new())
+                };
+
+                match res {
+                    Ok((entry, dir)) => {
+                        if let Some(entry) = entry {
+                            found.push(Ok(entry));
+                        }
+
+                        if let Some(dir) = dir {
+                            dirs.push(dir);
+                        }
+                    }
+                    Err(err) => found.push(Err(err)),
+                }
+            }
+
+            let mut shared = state.lock().unwrap();
+
+            shared.active -= 1;
+            shared.found.extend(found);
+            shared.pending.append(&mut dirs);
+            cvar.notify_all();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env::temp_dir;
+    use std::fs::{create_dir_all, remove_dir_all, write};
+    use std::os::unix::fs::symlink;
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> TempDir {
+            let path = temp_dir().join(format!("inko-walker-{}", name));
+
+            if path.is_dir() {
+                remove_dir_all(&path).unwrap();
+            }
+
+            create_dir_all(path.join("a").join("b")).unwrap();
+            write(path.join("foo.inko"), "").unwrap();
+            write(path.join("a").join("bar.inko"), "").unwrap();
+            write(path.join("a").join("b").join("baz.txt"), "").unwrap();
+
+            TempDir(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = remove_dir_all(&self.0);
+        }
+    }
+
+    fn walk(mut walker: Walker) -> Vec<Entry> {
+        let mut entries = Vec::new();
+
+        while let Some(entry) = walker.next() {
+            entries.push(entry.unwrap());
+        }
+
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        entries
+    }
+
+    #[test]
+    fn test_walk_all() {
+        let dir = TempDir::new("all");
+        let entries = walk(Walker::new(dir.0.clone(), None, false, 1));
+
+        assert_eq!(
+            entries,
+            vec![
+                Entry { path: dir.0.join("a"), kind: EntryType::Directory },
+                Entry {
+                    path: dir.0.join("a").join("b"),
+                    kind: EntryType::Directory
+                },
+                Entry {
+                    path: dir.0.join("a").join("b").join("baz.txt"),
+                    kind: EntryType::File
+                },
+                Entry {
+                    path: dir.0.join("a").join("bar.inko"),
+                    kind: EntryType::File
+                },
+                Entry { path: dir.0.join("foo.inko"), kind: EntryType::File },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_walk_with_pattern() {
+        let dir = TempDir::new("pattern");
+        let pattern = Pattern::parse("**/*.inko").unwrap();
+        let entries = walk(Walker::new(dir.0.clone(), Some(pattern), false, 1));
+        let paths: Vec<_> = entries.into_iter().map(|e| e.path).collect();
+
+        assert_eq!(
+            paths,
+            vec![dir.0.join("a").join("bar.inko"), dir.0.join("foo.inko")]
+        );
+    }
+
+    #[test]
+    fn test_walk_parallel() {
+        let dir = TempDir::new("parallel");
+        let seq = walk(Walker::new(dir.0.clone(), None, false, 1));
+        let par = walk(Walker::new(dir.0.clone(), None, false, 4));
+
+        assert_eq!(seq, par);
+    }
+
+    #[test]
+    fn test_walk_missing_directory() {
+        let path = temp_dir().join("inko-walker-missing");
+        let mut walker = Walker::new(path, None, false, 1);
+
+        assert!(walker.next().unwrap().is_err());
+        assert!(walker.next().is_none());
+    }
+
+    #[test]
+    fn test_walk_symlink_loop() {
+        let dir = TempDir::new("loop");
+
+        symlink(&dir.0, dir.0.join("a").join("b").join("loop")).unwrap();
+
+        let link = dir.0.join("a").join("b").join("loop");
+        let without = walk(Walker::new(dir.0.clone(), None, false, 1));
+        let with = walk(Walker::new(dir.0.clone(), None, true, 1));
+        let par = walk(Walker::new(dir.0.clone(), None, true, 4));
+
+        assert!(without
+            .iter()
+            .any(|e| e.path == link && e.kind == EntryType::SymbolicLink));
+        assert!(with
+            .iter()
+            .any(|e| e.path == link && e.kind == EntryType::Directory));
+        assert_eq!(without.len(), 6);
+        assert_eq!(with.len(), 6);
+        assert_eq!(par.len(), 6);
+    }
+}
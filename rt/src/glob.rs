@@ -0,0 +1,281 @@
+//! Matching of file paths against glob patterns.
+//!
+//! The following syntax is supported:
+//!
+//! - `?` matches a single character, except for `/`
+//! - `*` matches zero or more characters, except for `/`
+//! - `**` as a whole path component matches zero or more path components
+//! - `[abc]` matches any of the characters in the set
+//! - `[a-z]` matches any character in the range
+//! - `[!abc]` and `[^abc]` match any character _not_ in the set
+//! - `\` escapes the character that follows it
+//!
+//! Patterns are always matched against the entire path, using `/` as the
+//! separator.
+
+/// The character used to separate path components.
+const SEPARATOR: char = '/';
+
+#[derive(Eq, PartialEq, Debug)]
+enum Token {
+    /// A literal character.
+    Char(char),
+
+    /// A single character (`?`).
+    Any,
+
+    /// Zero or more characters, excluding the separator (`*`).
+    Many,
+
+    /// Zero or more path components (`**/` or a trailing `/**`).
+    Components,
+
+    /// A set of characters (`[...]`). The boolean is set to `true` if the set
+    /// is negated.
+    Set(Vec<(char, char)>, bool),
+}
+
+/// A compiled glob pattern.
+#[derive(Eq, PartialEq, Debug)]
+pub(crate) struct Pattern {
+    tokens: Vec<Token>,
+}
+
+impl Pattern {
+    /// Parses a glob pattern.
+    ///
+    /// An error is returned if the pattern contains an unterminated character
+    /// set or a trailing escape character.
+    pub(crate) fn parse(pattern: &str) -> Result<Pattern, String> {
+        let chars: Vec<char> = pattern.chars().collect();
+        let mut tokens = Vec::new();
+        let mut idx = 0;
+
+        while idx < chars.len() {
+            match chars[idx] {
+                '?' => tokens.push(Token::Any),
+                '*' if chars.get(idx + 1) == Some(&'*') => {
+                    let start = idx == 0 || chars[idx - 1] == SEPARATOR;
+                    let next = chars.get(idx + 2);
+
+                    if start && next == Some(&SEPARATOR) {
+                        tokens.push(Token::Components);
+                        idx += 2;
+                    } else if start && next.is_none() {
+                        tokens.push(Token::Components);
+                        tokens.push(Token::Many);
+                        idx += 1;
+                    } else {
+                        // Something like `a**b` is treated the same as `a*b`.
+                        tokens.push(Token::Many);
+                        idx += 1;
+                    }
+                }
+                '*' => tokens.push(Token::Many),
+                '[' => {
+                    let (token, end) = Self::parse_set(&chars, idx)?;
+
+                    tokens.push(token);
+                    idx = end;
+                }
+                '\\' => {
+                    idx += 1;
+
+                    if let Some(&chr) = chars.get(idx) {
+                        tokens.push(Token::Char(chr));
+                    } else {
+                        return Err(
+                            "the pattern ends with an escape character"
+                                .to_string(),
+                        );
+                    }
+                }
+                chr => tokens.push(Token::Char(chr)),
+            }
+
+            idx += 1;
+        }
+
+        Ok(Pattern { tokens })
+    }
+
+    /// Returns `true` if the path matches the pattern.
+    pub(crate) fn matches(&self, path: &str) -> bool {
+        let chars: Vec<char> = path.chars().collect();
+
+        Self::matches_from(&self.tokens, &chars)
+    }
+
+    fn parse_set(
+        chars: &[char],
+        start: usize,
+    ) -> Result<(Token, usize), String> {
+        let mut idx = start + 1;
+        let negated = matches!(chars.get(idx), Some('!') | Some('^'));
+        let mut ranges = Vec::new();
+
+        if negated {
+            idx += 1;
+        }
+
+        // A `]` directly after the opening bracket is treated as a literal.
+        if chars.get(idx) == Some(&']') {
+            ranges.push((']', ']'));
+            idx += 1;
+        }
+
+        while let Some(&chr) = chars.get(idx) {
+            if chr == ']' {
+                return Ok((Token::Set(ranges, negated), idx));
+            }
+
+            if chars.get(idx + 1) == Some(&'-')
+                && chars.get(idx + 2).map_or(false, |&c| c != ']')
+            {
+                ranges.push((chr, chars[idx + 2]));
+                idx += 3;
+            } else {
+                ranges.push((chr, chr));
+                idx += 1;
+            }
+        }
+
+        Err(format!(
+            "the character set starting at offset {} isn't terminated",
+            start
+        ))
+    }
+
+    fn matches_from(tokens: &[Token], path: &[char]) -> bool {
+        let (token, rest) = match tokens.split_first() {
+            Some(v) => v,
+            None => return path.is_empty(),
+        };
+
+        match token {
+            Token::Char(chr) => {
+                path.first() == Some(chr)
+                    && Self::matches_from(rest, &path[1..])
+            }
+            Token::Any => match path.first() {
+                Some(&chr) if chr != SEPARATOR => {
+                    Self::matches_from(rest, &path[1..])
+                }
+                _ => false,
+            },
+            Token::Set(ranges, negated) => match path.first() {
+                Some(&chr) if chr != SEPARATOR => {
+                    let found = ranges
+                        .iter()
+                        .any(|&(start, end)| chr >= start && chr <= end);
+
+                    found != *negated && Self::matches_from(rest, &path[1..])
+                }
+                _ => false,
+            },
+            Token::Many => {
+                let mut idx = 0;
+
+                loop {
+                    if Self::matches_from(rest, &path[idx..]) {
+                        return true;
+                    }
+
+                    if idx == path.len() || path[idx] == SEPARATOR {
+                        return false;
+                    }
+
+                    idx += 1;
+                }
+            }
+            Token::Components => {
+                let mut idx = 0;
+
+                loop {
+                    if Self::matches_from(rest, &path[idx..]) {
+                        return true;
+                    }
+
+                    // Skip to the start of the next component.
+                    match path[idx..].iter().position(|&c| c == SEPARATOR) {
+                        Some(pos) => idx += pos + 1,
+                        None => return false,
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, path: &str) -> bool {
+        Pattern::parse(pattern).unwrap().matches(path)
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(Pattern::parse("[abc").is_err());
+        assert!(Pattern::parse("abc\\").is_err());
+    }
+
+    #[test]
+    fn test_literal() {
+        assert!(matches("foo", "foo"));
+        assert!(matches("foo/bar", "foo/bar"));
+        assert!(matches("", ""));
+        assert!(!matches("foo", "foobar"));
+        assert!(!matches("foo", "fo"));
+    }
+
+    #[test]
+    fn test_any() {
+        assert!(matches("f?o", "foo"));
+        assert!(!matches("f?o", "fo"));
+        assert!(!matches("a?b", "a/b"));
+    }
+
+    #[test]
+    fn test_many() {
+        assert!(matches("*.inko", "foo.inko"));
+        assert!(matches("*.inko", ".inko"));
+        assert!(matches("f*o*r", "foobar"));
+        assert!(!matches("*.inko", "foo/bar.inko"));
+        assert!(!matches("*.inko", "foo.rs"));
+        assert!(matches("a**b", "axyzb"));
+    }
+
+    #[test]
+    fn test_components() {
+        assert!(matches("**/*.inko", "foo.inko"));
+        assert!(matches("**/*.inko", "a/b/c/foo.inko"));
+        assert!(matches("src/**/foo", "src/foo"));
+        assert!(matches("src/**/foo", "src/a/b/foo"));
+        assert!(matches("src/**", "src/a/b"));
+        assert!(matches("**", "a/b/c"));
+        assert!(!matches("src/**/foo", "test/a/foo"));
+        assert!(!matches("**/*.inko", "a/b/foo.rs"));
+    }
+
+    #[test]
+    fn test_sets() {
+        assert!(matches("[abc].txt", "b.txt"));
+        assert!(matches("[a-c]x", "cx"));
+        assert!(matches("[!a-c]x", "dx"));
+        assert!(matches("[^a]x", "bx"));
+        assert!(matches("[]]", "]"));
+        assert!(matches("[a-]", "-"));
+        assert!(!matches("[abc].txt", "d.txt"));
+        assert!(!matches("[!a-c]x", "bx"));
+        assert!(!matches("[!a]", "/"));
+    }
+
+    #[test]
+    fn test_escape() {
+        assert!(matches("\\*", "*"));
+        assert!(!matches("\\*", "a"));
+        assert!(matches("\\[a]", "[a]"));
+    }
+}
@@ -8,6 +8,9 @@ pub mod macros;
 pub mod arc_without_weak;
 pub mod config;
 pub mod context;
+pub mod decimal;
+pub mod directory_walker;
+pub mod glob;
 pub mod mem;
 pub mod memory_map;
 pub mod network_poller;
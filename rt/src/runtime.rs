@@ -1,5 +1,6 @@
 mod byte_array;
 mod class;
+mod decimal;
 mod env;
 mod float;
 mod fs;
@@ -13,6 +14,7 @@ mod socket;
 mod stdio;
 mod string;
 mod sys;
+mod terminal;
 mod time;
 
 use crate::config::Config;
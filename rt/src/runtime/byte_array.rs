@@ -10,6 +10,14 @@ pub unsafe extern "system" fn inko_byte_array_new(
     ByteArray::alloc((*state).byte_array_class, Vec::new())
 }
 
+#[no_mangle]
+pub unsafe extern "system" fn inko_byte_array_with_capacity(
+    state: *const State,
+    size: i64,
+) -> *mut ByteArray {
+    ByteArray::alloc((*state).byte_array_class, Vec::with_capacity(size as _))
+}
+
 #[no_mangle]
 pub unsafe extern "system" fn inko_byte_array_push(
     bytes: *mut ByteArray,
@@ -145,6 +153,25 @@ pub unsafe extern "system" fn inko_byte_array_copy_from(
     amount
 }
 
+#[no_mangle]
+pub unsafe extern "system" fn inko_byte_array_index_of(
+    bytes: *const ByteArray,
+    byte: i64,
+    start: i64,
+) -> i64 {
+    let bytes = &(*bytes).value;
+
+    if start < 0 || start as usize >= bytes.len() {
+        return -1;
+    }
+
+    let start = start as usize;
+
+    memchr::memchr(byte as u8, &bytes[start..])
+        .map(|idx| (idx + start) as i64)
+        .unwrap_or(-1)
+}
+
 #[no_mangle]
 pub unsafe extern "system" fn inko_byte_array_resize(
     bytes: *mut ByteArray,
@@ -154,6 +181,21 @@ pub unsafe extern "system" fn inko_byte_array_resize(
     (*bytes).value.resize(size as usize, filler as u8);
 }
 
+#[no_mangle]
+pub unsafe extern "system" fn inko_byte_array_reserve(
+    bytes: *mut ByteArray,
+    size: i64,
+) {
+    (*bytes).value.reserve(size as usize);
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn inko_byte_array_capacity(
+    bytes: *const ByteArray,
+) -> i64 {
+    (*bytes).value.capacity() as i64
+}
+
 #[no_mangle]
 pub unsafe extern "system" fn inko_byte_array_to_pointer(
     bytes: *mut ByteArray,
@@ -0,0 +1,127 @@
+use crate::decimal::{Decimal, Rounding};
+use crate::mem::String as InkoString;
+use crate::result::Result as InkoResult;
+use crate::state::State;
+use std::cmp::Ordering;
+
+fn alloc(decimal: Decimal) -> *mut Decimal {
+    Box::into_raw(Box::new(decimal))
+}
+
+fn scale(value: i64) -> u32 {
+    value.clamp(0, u32::MAX as i64) as u32
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn inko_decimal_parse(
+    input: *const InkoString,
+) -> InkoResult {
+    Decimal::parse(InkoString::read(input))
+        .map(InkoResult::ok_boxed)
+        .unwrap_or_else(InkoResult::none)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn inko_decimal_from_int(
+    value: i64,
+) -> *mut Decimal {
+    alloc(Decimal::from_int(value))
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn inko_decimal_add(
+    left: *const Decimal,
+    right: *const Decimal,
+) -> *mut Decimal {
+    alloc((*left).add(&*right))
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn inko_decimal_sub(
+    left: *const Decimal,
+    right: *const Decimal,
+) -> *mut Decimal {
+    alloc((*left).sub(&*right))
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn inko_decimal_mul(
+    left: *const Decimal,
+    right: *const Decimal,
+) -> InkoResult {
+    (*left)
+        .mul(&*right)
+        .map(InkoResult::ok_boxed)
+        .unwrap_or_else(InkoResult::none)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn inko_decimal_div(
+    left: *const Decimal,
+    right: *const Decimal,
+    scale_value: i64,
+    rounding: i64,
+) -> InkoResult {
+    (*left)
+        .div(&*right, scale(scale_value), Rounding::from_int(rounding))
+        .map(InkoResult::ok_boxed)
+        .unwrap_or_else(InkoResult::none)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn inko_decimal_round(
+    decimal: *const Decimal,
+    scale_value: i64,
+    rounding: i64,
+) -> InkoResult {
+    (*decimal)
+        .round(scale(scale_value), Rounding::from_int(rounding))
+        .map(InkoResult::ok_boxed)
+        .unwrap_or_else(InkoResult::none)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn inko_decimal_negate(
+    decimal: *const Decimal,
+) -> *mut Decimal {
+    alloc((*decimal).negate())
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn inko_decimal_compare(
+    left: *const Decimal,
+    right: *const Decimal,
+) -> i64 {
+    match (*left).compare(&*right) {
+        Ordering::Less => -1,
+        Ordering::Equal => 0,
+        Ordering::Greater => 1,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn inko_decimal_scale(
+    decimal: *const Decimal,
+) -> i64 {
+    (*decimal).scale() as i64
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn inko_decimal_to_string(
+    state: *const State,
+    decimal: *const Decimal,
+) -> *const InkoString {
+    InkoString::alloc((*state).string_class, (*decimal).to_string())
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn inko_decimal_clone(
+    decimal: *const Decimal,
+) -> *mut Decimal {
+    alloc((*decimal).clone())
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn inko_decimal_drop(decimal: *mut Decimal) {
+    drop(Box::from_raw(decimal));
+}
@@ -1,20 +1,58 @@
 use crate::mem::String as InkoString;
 use crate::state::State;
 
+/// The maximum number of decimals to produce when formatting a float.
+///
+/// This is the number of decimal digits needed to represent the smallest
+/// subnormal f64 exactly, so going beyond this only adds trailing zeros.
+const MAX_DECIMALS: i64 = 1074;
+
+fn special_float(value: f64) -> Option<&'static str> {
+    if value.is_infinite() && value.is_sign_positive() {
+        Some("Infinity")
+    } else if value.is_infinite() {
+        Some("-Infinity")
+    } else if value.is_nan() {
+        Some("NaN")
+    } else {
+        None
+    }
+}
+
 #[no_mangle]
 pub unsafe extern "system" fn inko_float_to_string(
     state: *const State,
     value: f64,
 ) -> *const InkoString {
-    let string = if value.is_infinite() && value.is_sign_positive() {
-        "Infinity".to_string()
-    } else if value.is_infinite() {
-        "-Infinity".to_string()
-    } else if value.is_nan() {
-        "NaN".to_string()
-    } else {
-        format!("{:?}", value)
-    };
+    let string = special_float(value)
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| format!("{:?}", value));
+
+    InkoString::alloc((*state).string_class, string)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn inko_float_to_fixed_string(
+    state: *const State,
+    value: f64,
+    decimals: i64,
+) -> *const InkoString {
+    let decimals = decimals.clamp(0, MAX_DECIMALS) as usize;
+    let string = special_float(value)
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| format!("{:.*}", decimals, value));
+
+    InkoString::alloc((*state).string_class, string)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn inko_float_to_scientific_string(
+    state: *const State,
+    value: f64,
+) -> *const InkoString {
+    let string = special_float(value)
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| format!("{:e}", value));
 
     InkoString::alloc((*state).string_class, string)
 }
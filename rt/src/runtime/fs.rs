@@ -1,13 +1,27 @@
+use crate::directory_walker::Walker;
+use crate::glob::Pattern;
 use crate::mem::{ByteArray, String as InkoString};
 use crate::process::ProcessPointer;
 use crate::result::Result as InkoResult;
 use crate::runtime::helpers::read_into;
 use crate::state::State;
-use std::fs::{self, File, OpenOptions};
+use rand::distributions::Alphanumeric;
+use rand::{thread_rng, Rng};
+use std::fs::{self, DirBuilder, File, OpenOptions};
 use std::io::{self, Seek, SeekFrom, Write};
-use std::path::PathBuf;
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+use std::path::{Component, Path, PathBuf};
+use std::thread::available_parallelism;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// The number of random characters to use for temporary file and directory
+/// names.
+const TEMPORARY_NAME_SIZE: usize = 12;
+
+/// The number of times we'll try to find an unused name for a temporary file or
+/// directory before giving up.
+const TEMPORARY_ATTEMPTS: usize = 128;
+
 #[no_mangle]
 pub unsafe extern "system" fn inko_file_drop(file: *mut File) {
     drop(Box::from_raw(file));
@@ -146,6 +160,36 @@ pub unsafe extern "system" fn inko_path_expand(
         .unwrap_or_else(InkoResult::io_error)
 }
 
+#[no_mangle]
+pub unsafe extern "system" fn inko_path_normalize(
+    state: *const State,
+    path: *const InkoString,
+) -> *const InkoString {
+    let path = normalize(Path::new(InkoString::read(path)));
+
+    InkoString::alloc(
+        (*state).string_class,
+        path.to_string_lossy().into_owned(),
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn inko_path_relative_to(
+    state: *const State,
+    path: *const InkoString,
+    base: *const InkoString,
+) -> InkoResult {
+    let path = Path::new(InkoString::read(path));
+    let base = Path::new(InkoString::read(base));
+
+    relative_to(path, base).map_or_else(InkoResult::none, |p| {
+        InkoResult::ok(InkoString::alloc(
+            (*state).string_class,
+            p.to_string_lossy().into_owned(),
+        ) as _)
+    })
+}
+
 #[no_mangle]
 pub unsafe extern "system" fn inko_path_is_file(
     process: ProcessPointer,
@@ -267,6 +311,226 @@ pub unsafe extern "system" fn inko_directory_remove_recursive(
         .unwrap_or_else(InkoResult::io_error)
 }
 
+#[no_mangle]
+pub unsafe extern "system" fn inko_file_create_temporary(
+    process: ProcessPointer,
+    directory: *const InkoString,
+    prefix: *const InkoString,
+    path: *mut ByteArray,
+) -> InkoResult {
+    let dir = InkoString::read(directory);
+    let prefix = InkoString::read(prefix);
+    let mut opts = OpenOptions::new();
+
+    // The file is only accessible to the current user, similar to mkstemp(3).
+    opts.read(true).write(true).create_new(true).mode(0o600);
+
+    process
+        .blocking(|| create_temporary(dir, prefix, |p| opts.open(p)))
+        .map(|(file, tmp_path)| {
+            write_path(tmp_path, &mut *path);
+            InkoResult::ok_boxed(file)
+        })
+        .unwrap_or_else(InkoResult::io_error)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn inko_directory_create_temporary(
+    process: ProcessPointer,
+    directory: *const InkoString,
+    prefix: *const InkoString,
+    path: *mut ByteArray,
+) -> InkoResult {
+    let dir = InkoString::read(directory);
+    let prefix = InkoString::read(prefix);
+    let mut builder = DirBuilder::new();
+
+    builder.mode(0o700);
+
+    process
+        .blocking(|| create_temporary(dir, prefix, |p| builder.create(p)))
+        .map(|(_, tmp_path)| {
+            write_path(tmp_path, &mut *path);
+            InkoResult::none()
+        })
+        .unwrap_or_else(InkoResult::io_error)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn inko_directory_walker_new(
+    path: *const InkoString,
+    pattern: *const InkoString,
+    follow_symlinks: bool,
+    threads: i64,
+) -> InkoResult {
+    let path = PathBuf::from(InkoString::read(path));
+    let pattern = match InkoString::read(pattern) {
+        "" => None,
+        raw => match Pattern::parse(raw) {
+            Ok(pattern) => Some(pattern),
+            Err(_) => {
+                return InkoResult::io_error(io::Error::from_raw_os_error(
+                    libc::EINVAL,
+                ))
+            }
+        },
+    };
+
+    // Using more threads than there are CPU cores doesn't make walking any
+    // faster, and spawning a large number of threads may fail, so the number
+    // of threads is limited to the number of cores.
+    let cores = available_parallelism().map(|v| v.get()).unwrap_or(1);
+    let threads = (threads.max(1) as usize).min(cores);
+
+    InkoResult::ok_boxed(Walker::new(path, pattern, follow_symlinks, threads))
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn inko_directory_walker_next(
+    state: *const State,
+    process: ProcessPointer,
+    walker: *mut Walker,
+    kind: *mut i64,
+) -> InkoResult {
+    let walker = &mut *walker;
+
+    match process.blocking(|| walker.next()) {
+        Some(Ok(entry)) => {
+            let path = entry.path.to_string_lossy().into_owned();
+
+            *kind = entry.kind as i64;
+            InkoResult::ok(InkoString::alloc((*state).string_class, path) as _)
+        }
+        Some(Err(err)) => InkoResult::io_error(err),
+        None => InkoResult::none(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn inko_directory_walker_drop(walker: *mut Walker) {
+    drop(Box::from_raw(walker));
+}
+
+/// Creates a file or directory using a random name, retrying with a different
+/// name if the chosen name is already in use.
+///
+/// The `create` closure is expected to fail with `AlreadyExists` if the path
+/// already exists, instead of reusing it. This ensures that two processes can't
+/// end up using the same temporary path.
+fn create_temporary<T, F: FnMut(&Path) -> io::Result<T>>(
+    directory: &str,
+    prefix: &str,
+    mut create: F,
+) -> io::Result<(T, PathBuf)> {
+    let mut rng = thread_rng();
+
+    for _ in 0..TEMPORARY_ATTEMPTS {
+        let mut name = prefix.to_string();
+
+        name.extend(
+            (&mut rng)
+                .sample_iter(&Alphanumeric)
+                .take(TEMPORARY_NAME_SIZE)
+                .map(char::from),
+        );
+
+        let path = Path::new(directory).join(name);
+
+        match create(&path) {
+            Ok(value) => return Ok((value, path)),
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(err),
+        }
+    }
+
+    Err(io::Error::from(io::ErrorKind::AlreadyExists))
+}
+
+/// Normalizes a path without accessing the file system.
+///
+/// This removes redundant separators and `.` components, and resolves `..`
+/// components by removing the preceding component. For absolute paths, `..`
+/// components at the root are removed, while for relative paths leading `..`
+/// components are retained.
+///
+/// If the result is empty, a `.` is returned.
+fn normalize(path: &Path) -> PathBuf {
+    let mut parts: Vec<Component> = Vec::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match parts.last() {
+                Some(Component::Normal(_)) => {
+                    parts.pop();
+                }
+                Some(Component::RootDir | Component::Prefix(_)) => {}
+                _ => parts.push(component),
+            },
+            _ => parts.push(component),
+        }
+    }
+
+    if parts.is_empty() {
+        return PathBuf::from(".");
+    }
+
+    parts.into_iter().collect()
+}
+
+/// Returns a path that, when joined with `base`, produces the same path as
+/// `path`.
+///
+/// Both paths are normalized first. If one path is absolute and the other is
+/// relative, or if the result can't be determined without accessing the file
+/// system (e.g. `base` is `..`), a `None` is returned.
+fn relative_to(path: &Path, base: &Path) -> Option<PathBuf> {
+    if path.is_absolute() != base.is_absolute() {
+        return None;
+    }
+
+    let path = normalize(path);
+    let base = normalize(base);
+    let mut path_iter = path.components().peekable();
+    let mut base_iter = base.components().peekable();
+
+    // Skip the components both paths have in common.
+    while let (Some(a), Some(b)) = (path_iter.peek(), base_iter.peek()) {
+        if a != b {
+            break;
+        }
+
+        path_iter.next();
+        base_iter.next();
+    }
+
+    let mut result = PathBuf::new();
+
+    for component in base_iter {
+        match component {
+            Component::Normal(_) => result.push(".."),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+
+    for component in path_iter {
+        if component != Component::CurDir {
+            result.push(component);
+        }
+    }
+
+    if result.as_os_str().is_empty() {
+        result.push(".");
+    }
+
+    Some(result)
+}
+
+fn write_path(path: PathBuf, buffer: &mut ByteArray) {
+    buffer.value.extend_from_slice(path.to_string_lossy().as_bytes());
+}
+
 unsafe fn open_file(
     process: ProcessPointer,
     options: OpenOptions,
@@ -286,3 +550,117 @@ fn system_time_to_timestamp(time: SystemTime) -> f64 {
 
     duration.unwrap().as_secs_f64()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env::temp_dir;
+
+    #[test]
+    fn test_create_temporary() {
+        let dir = temp_dir();
+        let dir_str = dir.to_string_lossy();
+        let (_, path) =
+            create_temporary(&dir_str, "inko-", |p| fs::create_dir(p)).unwrap();
+        let name = path.file_name().unwrap().to_string_lossy();
+
+        assert!(path.is_dir());
+        assert!(path.starts_with(&dir));
+        assert!(name.starts_with("inko-"));
+        assert_eq!(name.len(), 5 + TEMPORARY_NAME_SIZE);
+
+        fs::remove_dir(path).unwrap();
+    }
+
+    #[test]
+    fn test_create_temporary_with_existing_paths() {
+        let mut attempts = 0;
+        let res = create_temporary("/tmp", "inko-", |_| {
+            attempts += 1;
+
+            if attempts < 4 {
+                Err(io::Error::from(io::ErrorKind::AlreadyExists))
+            } else {
+                Ok(attempts)
+            }
+        });
+
+        assert_eq!(res.unwrap().0, 4);
+    }
+
+    #[test]
+    fn test_create_temporary_with_other_errors() {
+        let mut attempts = 0;
+        let res: io::Result<((), PathBuf)> =
+            create_temporary("/tmp", "inko-", |_| {
+                attempts += 1;
+                Err(io::Error::from(io::ErrorKind::PermissionDenied))
+            });
+
+        assert_eq!(res.unwrap_err().kind(), io::ErrorKind::PermissionDenied);
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn test_create_temporary_gives_up() {
+        let res: io::Result<((), PathBuf)> =
+            create_temporary("/tmp", "inko-", |_| {
+                Err(io::Error::from(io::ErrorKind::AlreadyExists))
+            });
+
+        assert_eq!(res.unwrap_err().kind(), io::ErrorKind::AlreadyExists);
+    }
+
+    #[test]
+    fn test_normalize() {
+        let cases = [
+            ("", "."),
+            (".", "."),
+            ("./", "."),
+            ("foo", "foo"),
+            ("foo/", "foo"),
+            ("foo//bar", "foo/bar"),
+            ("foo/./bar", "foo/bar"),
+            ("foo/../bar", "bar"),
+            ("foo/bar/..", "foo"),
+            ("foo/..", "."),
+            ("../foo", "../foo"),
+            ("../../foo/..", "../.."),
+            ("/", "/"),
+            ("/..", "/"),
+            ("/../foo", "/foo"),
+            ("/foo/bar/../baz", "/foo/baz"),
+        ];
+
+        for (input, output) in cases {
+            assert_eq!(normalize(Path::new(input)), PathBuf::from(output));
+        }
+    }
+
+    #[test]
+    fn test_relative_to() {
+        let cases = [
+            ("/foo/bar", "/foo", Some("bar")),
+            ("/foo/bar", "/foo/bar", Some(".")),
+            ("/foo", "/foo/bar", Some("..")),
+            ("/foo/bar", "/baz/quix", Some("../../foo/bar")),
+            ("/foo/./bar/", "/foo/baz/..", Some("bar")),
+            ("foo/bar", "foo", Some("bar")),
+            ("foo", "bar", Some("../foo")),
+            ("../foo", "..", Some("foo")),
+            ("foo", "..", None),
+            ("/foo", "foo", None),
+            ("foo", "/foo", None),
+        ];
+
+        for (path, base, output) in cases {
+            assert_eq!(
+                relative_to(Path::new(path), Path::new(base)),
+                output.map(PathBuf::from),
+                "{} relative to {}",
+                path,
+                base
+            );
+        }
+    }
+}
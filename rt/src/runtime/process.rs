@@ -10,24 +10,26 @@ use crate::scheduler::process::Action;
 use crate::scheduler::timeouts::Timeout;
 use crate::state::State;
 use std::cmp::max;
+use std::env::var;
 use std::fmt::Write as _;
 use std::str;
 use std::time::Duration;
 
-/// Terminates the current program with an Inko panic (opposed to a panic
-/// triggered using the `panic!` macro).
-///
-/// This function is marked as cold as we expect it to be called rarely, if ever
-/// (in a correct program). This should also ensure any branches leading to this
-/// function are treated as unlikely.
-#[inline(never)]
-#[cold]
-pub(crate) fn panic(process: ProcessPointer, message: &str) -> ! {
+/// The environment variable used to specify the format of panic messages.
+const PANIC_FORMAT: &str = "INKO_PANIC_FORMAT";
+
+/// Formats a panic as human readable text.
+fn text_panic(
+    process: &str,
+    id: usize,
+    message: &str,
+    frames: &[StackFrame],
+) -> String {
     let mut buffer = String::new();
 
     buffer.push_str("Stack trace (the most recent call comes last):");
 
-    for frame in process.stacktrace() {
+    for frame in frames {
         let _ = if !frame.path.is_empty() && frame.line > 0 {
             write!(
                 buffer,
@@ -42,12 +44,92 @@ pub(crate) fn panic(process: ProcessPointer, message: &str) -> ! {
     let _ = write!(
         buffer,
         "\nProcess '{}' ({:#x}) panicked: {}",
-        process.header.class.name,
-        process.identifier(),
-        message
+        process, id, message
     );
 
-    eprintln!("{}", buffer);
+    buffer
+}
+
+/// Formats a panic as a single line JSON object, for consumption by tools
+/// such as log aggregators.
+fn json_panic(
+    process: &str,
+    id: usize,
+    message: &str,
+    frames: &[StackFrame],
+) -> String {
+    let mut buffer = String::new();
+
+    buffer.push_str("{\"process\":");
+    json_string(&mut buffer, process);
+    let _ = write!(buffer, ",\"id\":\"{:#x}\",\"message\":", id);
+    json_string(&mut buffer, message);
+    buffer.push_str(",\"stacktrace\":[");
+
+    for (index, frame) in frames.iter().enumerate() {
+        if index > 0 {
+            buffer.push(',');
+        }
+
+        buffer.push_str("{\"name\":");
+        json_string(&mut buffer, &frame.name);
+
+        if !frame.path.is_empty() && frame.line > 0 {
+            buffer.push_str(",\"path\":");
+            json_string(&mut buffer, &frame.path);
+            let _ = write!(buffer, ",\"line\":{}}}", frame.line);
+        } else {
+            buffer.push_str(",\"path\":null,\"line\":null}");
+        }
+    }
+
+    buffer.push_str("]}");
+    buffer
+}
+
+fn json_string(buffer: &mut String, value: &str) {
+    buffer.push('"');
+
+    for chr in value.chars() {
+        match chr {
+            '"' => buffer.push_str("\\\""),
+            '\\' => buffer.push_str("\\\\"),
+            '\n' => buffer.push_str("\\n"),
+            '\r' => buffer.push_str("\\r"),
+            '\t' => buffer.push_str("\\t"),
+            chr if (chr as u32) < 0x20 => {
+                let _ = write!(buffer, "\\u{:04x}", chr as u32);
+            }
+            chr => buffer.push(chr),
+        }
+    }
+
+    buffer.push('"');
+}
+
+/// Terminates the current program with an Inko panic (opposed to a panic
+/// triggered using the `panic!` macro).
+///
+/// The panic is written to STDERR as text, or as JSON if the environment
+/// variable `INKO_PANIC_FORMAT` is set to "json".
+///
+/// This function is marked as cold as we expect it to be called rarely, if ever
+/// (in a correct program). This should also ensure any branches leading to this
+/// function are treated as unlikely.
+#[inline(never)]
+#[cold]
+pub(crate) fn panic(process: ProcessPointer, message: &str) -> ! {
+    let frames = process.stacktrace();
+    let name = &process.header.class.name;
+    let id = process.identifier();
+    let output = match var(PANIC_FORMAT) {
+        Ok(format) if format == "json" => {
+            json_panic(name, id, message, &frames)
+        }
+        _ => text_panic(name, id, message, &frames),
+    };
+
+    eprintln!("{}", output);
 
     // There's no real standard across programs for exit codes. Rust uses 101 so
     // for the sake of "we don't know a better value", we also use 101.
@@ -311,3 +393,60 @@ pub unsafe extern "system" fn inko_channel_receive_until(
 pub unsafe extern "system" fn inko_channel_drop(channel: *mut Channel) {
     Channel::drop(channel);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frames() -> Vec<StackFrame> {
+        vec![
+            StackFrame {
+                name: "main.Main.main".to_string(),
+                path: "/tmp/main.inko".to_string(),
+                line: 5,
+            },
+            StackFrame {
+                name: "foo".to_string(),
+                path: String::new(),
+                line: 0,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_text_panic() {
+        assert_eq!(
+            text_panic("Main", 0x10, "oops", &frames()),
+            "Stack trace (the most recent call comes last):\n  \
+            /tmp/main.inko:5 in main.Main.main\n  \
+            ?? in foo\n\
+            Process 'Main' (0x10) panicked: oops"
+        );
+    }
+
+    #[test]
+    fn test_json_panic() {
+        assert_eq!(
+            json_panic("Main", 0x10, "oops", &frames()),
+            "{\"process\":\"Main\",\"id\":\"0x10\",\"message\":\"oops\",\
+            \"stacktrace\":[\
+            {\"name\":\"main.Main.main\",\"path\":\"/tmp/main.inko\",\
+            \"line\":5},\
+            {\"name\":\"foo\",\"path\":null,\"line\":null}]}"
+        );
+        assert_eq!(
+            json_panic("Main", 0x10, "oops", &[]),
+            "{\"process\":\"Main\",\"id\":\"0x10\",\"message\":\"oops\",\
+            \"stacktrace\":[]}"
+        );
+    }
+
+    #[test]
+    fn test_json_string() {
+        let mut buffer = String::new();
+
+        json_string(&mut buffer, "a\"b\\c\nd\r\te\u{1}f");
+
+        assert_eq!(buffer, "\"a\\\"b\\\\c\\nd\\r\\te\\u0001f\"");
+    }
+}
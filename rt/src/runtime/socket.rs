@@ -6,6 +6,7 @@ use crate::result::{error_to_int, Result};
 use crate::scheduler::timeouts::Timeout;
 use crate::socket::Socket;
 use crate::state::State;
+use std::fs::File;
 use std::io::{self, Write};
 use std::ptr::{drop_in_place, write};
 
@@ -115,6 +116,48 @@ pub(crate) unsafe extern "system" fn inko_socket_write(
     .unwrap_or_else(Result::io_error)
 }
 
+#[no_mangle]
+pub(crate) unsafe extern "system" fn inko_socket_write_vectored(
+    state: *const State,
+    process: ProcessPointer,
+    socket: *mut Socket,
+    buffers: *const *const ByteArray,
+    length: i64,
+    offset: i64,
+    deadline: i64,
+) -> Result {
+    let state = &*state;
+    let buffers: Vec<&[u8]> = std::slice::from_raw_parts(buffers, length as _)
+        .iter()
+        .map(|&buf| (*buf).value.as_slice())
+        .collect();
+
+    blocking(state, process, &mut *socket, Interest::Write, deadline, |sock| {
+        sock.send_vectored(&buffers, offset as _)
+    })
+    .map(|v| Result::ok(v as _))
+    .unwrap_or_else(Result::io_error)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn inko_socket_send_file(
+    state: *const State,
+    process: ProcessPointer,
+    socket: *mut Socket,
+    file: *mut File,
+    size: i64,
+    deadline: i64,
+) -> Result {
+    let state = &*state;
+    let file = &mut *file;
+
+    blocking(state, process, &mut *socket, Interest::Write, deadline, |sock| {
+        sock.send_file(file, size as _)
+    })
+    .map(|v| Result::ok(v as _))
+    .unwrap_or_else(Result::io_error)
+}
+
 #[no_mangle]
 pub unsafe extern "system" fn inko_socket_read(
     state: *const State,
@@ -1,6 +1,7 @@
 use crate::mem::{ByteArray, String as InkoString};
 use crate::result::Result as InkoResult;
 use crate::state::State;
+use memchr::memmem;
 use std::cmp::min;
 use std::ffi::CStr;
 use std::os::raw::c_char;
@@ -145,6 +146,26 @@ pub unsafe extern "system" fn inko_string_chars_drop(iter: *mut u8) {
     drop(Box::from_raw(iter as *mut Graphemes));
 }
 
+#[no_mangle]
+pub unsafe extern "system" fn inko_string_byte_index(
+    string: *const InkoString,
+    find: *const InkoString,
+    start: i64,
+) -> i64 {
+    let haystack = InkoString::read(string).as_bytes();
+    let needle = InkoString::read(find).as_bytes();
+
+    if needle.is_empty() || start < 0 || start as usize >= haystack.len() {
+        return -1;
+    }
+
+    let start = start as usize;
+
+    memmem::find(&haystack[start..], needle)
+        .map(|idx| (idx + start) as i64)
+        .unwrap_or(-1)
+}
+
 #[no_mangle]
 pub unsafe extern "system" fn inko_string_slice_bytes_into(
     string: *const InkoString,
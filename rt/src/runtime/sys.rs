@@ -2,7 +2,8 @@ use crate::mem::{ByteArray, String as InkoString};
 use crate::process::ProcessPointer;
 use crate::result::Result as InkoResult;
 use crate::runtime::helpers::read_into;
-use std::io::Write;
+use std::io::{self, Write};
+use std::os::unix::process::CommandExt;
 use std::process::{Child, Command, Stdio};
 use std::slice;
 
@@ -14,6 +15,59 @@ fn stdio_for(value: i64) -> Stdio {
     }
 }
 
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+type Resource = libc::__rlimit_resource_t;
+
+#[cfg(not(all(target_os = "linux", target_env = "gnu")))]
+type Resource = libc::c_int;
+
+fn rlimit_resource(value: i64) -> Option<Resource> {
+    let res = match value {
+        0 => libc::RLIMIT_CPU,
+        1 => libc::RLIMIT_FSIZE,
+        2 => libc::RLIMIT_DATA,
+        3 => libc::RLIMIT_STACK,
+        4 => libc::RLIMIT_CORE,
+        5 => libc::RLIMIT_NOFILE,
+        6 => libc::RLIMIT_AS,
+        7 => libc::RLIMIT_NPROC,
+        _ => return None,
+    };
+
+    Some(res)
+}
+
+fn rlimit_value(value: i64) -> libc::rlim_t {
+    if value < 0 {
+        libc::RLIM_INFINITY
+    } else {
+        value as libc::rlim_t
+    }
+}
+
+#[no_mangle]
+pub(crate) unsafe extern "system" fn inko_limit_get(
+    resource: i64,
+    hard: bool,
+) -> InkoResult {
+    let Some(res) = rlimit_resource(resource) else {
+        return InkoResult::io_error(io::Error::from(
+            io::ErrorKind::InvalidInput,
+        ));
+    };
+
+    let mut limit = libc::rlimit { rlim_cur: 0, rlim_max: 0 };
+
+    if libc::getrlimit(res, &mut limit) != 0 {
+        return InkoResult::io_error(io::Error::last_os_error());
+    }
+
+    let value = if hard { limit.rlim_max } else { limit.rlim_cur };
+    let value = if value == libc::RLIM_INFINITY { -1 } else { value as i64 };
+
+    InkoResult::ok(value as _)
+}
+
 #[no_mangle]
 pub(crate) unsafe extern "system" fn inko_child_process_spawn(
     process: ProcessPointer,
@@ -26,17 +80,45 @@ pub(crate) unsafe extern "system" fn inko_child_process_spawn(
     stdout: i64,
     stderr: i64,
     directory: *const InkoString,
+    clear_env: bool,
+    limits: *const i64,
+    limits_length: i64,
+    new_session: bool,
 ) -> InkoResult {
     let program = InkoString::read(program);
     let args = slice::from_raw_parts(args, args_length as _);
     let env = slice::from_raw_parts(env, env_length as _);
     let directory = InkoString::read(directory);
     let mut cmd = Command::new(program);
+    let mut rlimits = Vec::new();
+
+    for triple in slice::from_raw_parts(limits, limits_length as _).chunks(3) {
+        let res = match rlimit_resource(triple[0]) {
+            Some(res) => res,
+            None => {
+                return InkoResult::io_error(io::Error::from(
+                    io::ErrorKind::InvalidInput,
+                ))
+            }
+        };
+
+        rlimits.push((
+            res,
+            libc::rlimit {
+                rlim_cur: rlimit_value(triple[1]),
+                rlim_max: rlimit_value(triple[2]),
+            },
+        ));
+    }
 
     for &ptr in args {
         cmd.arg(InkoString::read(ptr as _));
     }
 
+    if clear_env {
+        cmd.env_clear();
+    }
+
     for pair in env.chunks(2) {
         let key = InkoString::read(pair[0] as _);
         let val = InkoString::read(pair[1] as _);
@@ -52,6 +134,24 @@ pub(crate) unsafe extern "system" fn inko_child_process_spawn(
         cmd.current_dir(directory);
     }
 
+    if new_session || !rlimits.is_empty() {
+        // This closure runs in the child process after forking, so it must
+        // only use functions that are async-signal-safe.
+        cmd.pre_exec(move || {
+            if new_session && libc::setsid() == -1 {
+                return Err(io::Error::last_os_error());
+            }
+
+            for (res, limit) in &rlimits {
+                if libc::setrlimit(*res, limit) == -1 {
+                    return Err(io::Error::last_os_error());
+                }
+            }
+
+            Ok(())
+        });
+    }
+
     process
         .blocking(|| cmd.spawn())
         .map(InkoResult::ok_boxed)
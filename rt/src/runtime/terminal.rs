@@ -0,0 +1,340 @@
+use crate::mem::ByteArray;
+use crate::process::ProcessPointer;
+use crate::result::Result as InkoResult;
+use std::io;
+use std::mem::MaybeUninit;
+
+/// The number of bytes to read at once when reading key presses.
+const READ_SIZE: usize = 32;
+
+/// The byte used to start escape sequences.
+const ESC: u8 = 0x1b;
+
+/// A key read from a terminal in raw mode.
+///
+/// The numeric values of the variants are used by the standard library to
+/// construct the corresponding Inko values, so the order must stay fixed.
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub(crate) enum Key {
+    /// A regular character, stored as its Unicode code point.
+    Char(u32),
+
+    /// A letter combined with the Control key, such as Control+C.
+    Control(u32),
+    Enter,
+    Tab,
+    Backspace,
+    Escape,
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Insert,
+    Delete,
+
+    /// A function key, such as F1 (stored as 1).
+    Function(u32),
+
+    /// A byte sequence we don't know how to decode.
+    Unknown,
+}
+
+impl Key {
+    /// Returns the kind and value to expose to the standard library.
+    pub(crate) fn encode(self) -> (i64, i64) {
+        match self {
+            Key::Char(val) => (0, val as i64),
+            Key::Control(val) => (1, val as i64),
+            Key::Enter => (2, 0),
+            Key::Tab => (3, 0),
+            Key::Backspace => (4, 0),
+            Key::Escape => (5, 0),
+            Key::Up => (6, 0),
+            Key::Down => (7, 0),
+            Key::Left => (8, 0),
+            Key::Right => (9, 0),
+            Key::Home => (10, 0),
+            Key::End => (11, 0),
+            Key::PageUp => (12, 0),
+            Key::PageDown => (13, 0),
+            Key::Insert => (14, 0),
+            Key::Delete => (15, 0),
+            Key::Function(num) => (16, num as i64),
+            Key::Unknown => (17, 0),
+        }
+    }
+}
+
+/// Decodes the first key in the byte sequence.
+///
+/// The return value is the key and the number of bytes it occupies. If the
+/// input is empty or ends with an incomplete UTF-8 sequence, a `None` is
+/// returned.
+pub(crate) fn decode_key(bytes: &[u8]) -> Option<(Key, usize)> {
+    let first = *bytes.first()?;
+    let key = match first {
+        ESC => return Some(decode_escape(bytes)),
+        b'\r' | b'\n' => Key::Enter,
+        b'\t' => Key::Tab,
+        0x7f | 0x08 => Key::Backspace,
+        0x01..=0x1a => Key::Control((first - 1 + b'a') as u32),
+        0x00..=0x7f => Key::Char(first as u32),
+        _ => return decode_utf8(bytes),
+    };
+
+    Some((key, 1))
+}
+
+fn decode_utf8(bytes: &[u8]) -> Option<(Key, usize)> {
+    let size = match bytes[0] {
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf7 => 4,
+        _ => return Some((Key::Unknown, 1)),
+    };
+
+    if bytes.len() < size {
+        return None;
+    }
+
+    match std::str::from_utf8(&bytes[0..size]) {
+        Ok(s) => s.chars().next().map(|c| (Key::Char(c as u32), size)),
+        Err(_) => Some((Key::Unknown, 1)),
+    }
+}
+
+fn decode_escape(bytes: &[u8]) -> (Key, usize) {
+    match bytes.get(1) {
+        // CSI sequences, e.g. "\e[A".
+        Some(b'[') => decode_csi(bytes),
+        // SS3 sequences, e.g. "\eOP".
+        Some(b'O') => {
+            let key = match bytes.get(2) {
+                Some(b'A') => Key::Up,
+                Some(b'B') => Key::Down,
+                Some(b'C') => Key::Right,
+                Some(b'D') => Key::Left,
+                Some(b'H') => Key::Home,
+                Some(b'F') => Key::End,
+                Some(&b) if (b'P'..=b'S').contains(&b) => {
+                    Key::Function((b - b'P' + 1) as u32)
+                }
+                Some(_) => Key::Unknown,
+                None => return (Key::Escape, 1),
+            };
+
+            (key, 3)
+        }
+        _ => (Key::Escape, 1),
+    }
+}
+
+fn decode_csi(bytes: &[u8]) -> (Key, usize) {
+    // The parameters are digits separated by semicolons, followed by a
+    // single final byte in the range 0x40..=0x7E.
+    let mut end = 2;
+
+    while end < bytes.len() && !(0x40..=0x7e).contains(&bytes[end]) {
+        end += 1;
+    }
+
+    let final_byte = match bytes.get(end) {
+        Some(&b) => b,
+        None => return (Key::Escape, 1),
+    };
+
+    let params = std::str::from_utf8(&bytes[2..end]).unwrap_or("");
+    let number: u32 =
+        params.split(';').next().and_then(|v| v.parse().ok()).unwrap_or(0);
+
+    let key = match final_byte {
+        b'A' => Key::Up,
+        b'B' => Key::Down,
+        b'C' => Key::Right,
+        b'D' => Key::Left,
+        b'H' => Key::Home,
+        b'F' => Key::End,
+        b'~' => match number {
+            1 | 7 => Key::Home,
+            2 => Key::Insert,
+            3 => Key::Delete,
+            4 | 8 => Key::End,
+            5 => Key::PageUp,
+            6 => Key::PageDown,
+            11..=15 => Key::Function(number - 10),
+            17..=21 => Key::Function(number - 11),
+            23 | 24 => Key::Function(number - 12),
+            _ => Key::Unknown,
+        },
+        _ => Key::Unknown,
+    };
+
+    (key, end + 1)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn inko_terminal_is_terminal(fd: i64) -> bool {
+    libc::isatty(fd as _) == 1
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn inko_terminal_size(
+    fd: i64,
+    columns: *mut i64,
+    rows: *mut i64,
+) -> InkoResult {
+    let mut size = MaybeUninit::<libc::winsize>::zeroed();
+
+    if libc::ioctl(fd as _, libc::TIOCGWINSZ, size.as_mut_ptr()) == -1 {
+        return InkoResult::io_error(io::Error::last_os_error());
+    }
+
+    let size = size.assume_init();
+
+    *columns = size.ws_col as i64;
+    *rows = size.ws_row as i64;
+    InkoResult::none()
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn inko_terminal_enable_raw_mode(
+    fd: i64,
+) -> InkoResult {
+    let mut original = MaybeUninit::<libc::termios>::zeroed();
+
+    if libc::tcgetattr(fd as _, original.as_mut_ptr()) == -1 {
+        return InkoResult::io_error(io::Error::last_os_error());
+    }
+
+    let original = original.assume_init();
+    let mut raw = original;
+
+    libc::cfmakeraw(&mut raw);
+
+    // Output post-processing is left enabled, such that writing "\n" still
+    // moves the cursor to the start of the next line.
+    raw.c_oflag |= libc::OPOST;
+
+    if libc::tcsetattr(fd as _, libc::TCSANOW, &raw) == -1 {
+        return InkoResult::io_error(io::Error::last_os_error());
+    }
+
+    InkoResult::ok_boxed(original)
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn inko_terminal_disable_raw_mode(
+    fd: i64,
+    original: *mut libc::termios,
+) -> InkoResult {
+    let original = Box::from_raw(original);
+
+    if libc::tcsetattr(fd as _, libc::TCSANOW, &*original) == -1 {
+        InkoResult::io_error(io::Error::last_os_error())
+    } else {
+        InkoResult::none()
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "system" fn inko_terminal_read_key(
+    process: ProcessPointer,
+    fd: i64,
+    buffer: *mut ByteArray,
+    kind: *mut i64,
+) -> InkoResult {
+    let buffer = &mut (*buffer).value;
+
+    loop {
+        if let Some((key, size)) = decode_key(buffer) {
+            let (key_kind, value) = key.encode();
+
+            buffer.drain(0..size);
+            *kind = key_kind;
+            return InkoResult::ok(value as _);
+        }
+
+        let mut chunk = [0_u8; READ_SIZE];
+        // The error is captured in the closure, as other code may overwrite
+        // errno once the process is done blocking.
+        let res = process.blocking(|| {
+            match libc::read(fd as _, chunk.as_mut_ptr() as _, READ_SIZE) {
+                -1 => Err(io::Error::last_os_error()),
+                n => Ok(n as usize),
+            }
+        });
+
+        match res {
+            Ok(0) => return InkoResult::none(),
+            Ok(n) => buffer.extend_from_slice(&chunk[0..n]),
+            Err(e) => return InkoResult::io_error(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_key_empty() {
+        assert_eq!(decode_key(&[]), None);
+    }
+
+    #[test]
+    fn test_decode_key_ascii() {
+        assert_eq!(decode_key(b"ab"), Some((Key::Char('a' as u32), 1)));
+        assert_eq!(decode_key(b"\r"), Some((Key::Enter, 1)));
+        assert_eq!(decode_key(b"\t"), Some((Key::Tab, 1)));
+        assert_eq!(decode_key(&[0x7f]), Some((Key::Backspace, 1)));
+        assert_eq!(decode_key(&[0x03]), Some((Key::Control('c' as u32), 1)));
+    }
+
+    #[test]
+    fn test_decode_key_utf8() {
+        let bytes = "é".as_bytes();
+
+        assert_eq!(decode_key(bytes), Some((Key::Char('é' as u32), 2)));
+        assert_eq!(decode_key(&bytes[0..1]), None);
+        assert_eq!(decode_key(&[0xff]), Some((Key::Unknown, 1)));
+    }
+
+    #[test]
+    fn test_decode_key_escape() {
+        assert_eq!(decode_key(b"\x1b"), Some((Key::Escape, 1)));
+        assert_eq!(decode_key(b"\x1bx"), Some((Key::Escape, 1)));
+        assert_eq!(decode_key(b"\x1b["), Some((Key::Escape, 1)));
+    }
+
+    #[test]
+    fn test_decode_key_csi() {
+        assert_eq!(decode_key(b"\x1b[A"), Some((Key::Up, 3)));
+        assert_eq!(decode_key(b"\x1b[Bx"), Some((Key::Down, 3)));
+        assert_eq!(decode_key(b"\x1b[C"), Some((Key::Right, 3)));
+        assert_eq!(decode_key(b"\x1b[D"), Some((Key::Left, 3)));
+        assert_eq!(decode_key(b"\x1b[3~"), Some((Key::Delete, 4)));
+        assert_eq!(decode_key(b"\x1b[5~"), Some((Key::PageUp, 4)));
+        assert_eq!(decode_key(b"\x1b[15~"), Some((Key::Function(5), 5)));
+        assert_eq!(decode_key(b"\x1b[1;5A"), Some((Key::Up, 6)));
+        assert_eq!(decode_key(b"\x1b[99~"), Some((Key::Unknown, 5)));
+    }
+
+    #[test]
+    fn test_decode_key_ss3() {
+        assert_eq!(decode_key(b"\x1bOP"), Some((Key::Function(1), 3)));
+        assert_eq!(decode_key(b"\x1bOS"), Some((Key::Function(4), 3)));
+        assert_eq!(decode_key(b"\x1bOH"), Some((Key::Home, 3)));
+        assert_eq!(decode_key(b"\x1bO"), Some((Key::Escape, 1)));
+    }
+
+    #[test]
+    fn test_key_encode() {
+        assert_eq!(Key::Char(97).encode(), (0, 97));
+        assert_eq!(Key::Function(3).encode(), (16, 3));
+        assert_eq!(Key::Unknown.encode(), (17, 0));
+    }
+}
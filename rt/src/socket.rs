@@ -6,7 +6,9 @@ use crate::socket::socket_address::SocketAddress;
 use crate::state::State;
 use rustix::io::Errno;
 use socket2::{Domain, SockAddr, Socket as RawSocket, Type};
-use std::io::{self, Read};
+use std::cmp::min;
+use std::fs::File;
+use std::io::{self, IoSlice, Read};
 use std::mem::transmute;
 use std::net::Shutdown;
 use std::net::{IpAddr, SocketAddr};
@@ -17,6 +19,17 @@ use std::sync::atomic::{AtomicI8, Ordering};
 /// network poller.
 const NOT_REGISTERED: i8 = -1;
 
+/// The maximum number of buffers to write using a single vectored write.
+///
+/// This is the lowest value of IOV_MAX across the supported platforms. Any
+/// remaining buffers are written by subsequent writes.
+const MAX_IO_SLICES: usize = 1024;
+
+/// The maximum number of bytes to read from a file at once, when sending a file
+/// on platforms that don't support sendfile(2).
+#[cfg(not(target_os = "linux"))]
+const SEND_FILE_BUFFER_SIZE: usize = 64 * 1024;
+
 /// Decodes a SockAddr into an address/path, and a port.
 fn decode_sockaddr(
     sockaddr: SockAddr,
@@ -334,6 +347,80 @@ impl Socket {
         self.inner.shutdown(Shutdown::Both)
     }
 
+    /// Writes the given buffers using a single vectored write, returning the
+    /// number of bytes written.
+    ///
+    /// The first `offset` bytes of the buffers combined are skipped, allowing
+    /// callers to resume a partial write.
+    pub(crate) fn send_vectored(
+        &self,
+        buffers: &[&[u8]],
+        offset: usize,
+    ) -> io::Result<usize> {
+        let mut skip = offset;
+        let mut slices = Vec::with_capacity(min(buffers.len(), MAX_IO_SLICES));
+
+        for buf in buffers {
+            if slices.len() == MAX_IO_SLICES {
+                break;
+            }
+
+            if skip >= buf.len() {
+                skip -= buf.len();
+                continue;
+            }
+
+            slices.push(IoSlice::new(&buf[skip..]));
+            skip = 0;
+        }
+
+        self.inner.send_vectored(&slices)
+    }
+
+    /// Sends up to `size` bytes from the current position of `file`, advancing
+    /// the position of the file by the number of bytes sent.
+    #[cfg(target_os = "linux")]
+    pub(crate) fn send_file(
+        &self,
+        file: &mut File,
+        size: usize,
+    ) -> io::Result<usize> {
+        rustix::fs::sendfile(&self.inner, &*file, None, size)
+            .map_err(io::Error::from)
+    }
+
+    /// Sends up to `size` bytes from the current position of `file`, advancing
+    /// the position of the file by the number of bytes sent.
+    ///
+    /// This implementation copies the data through an intermediate buffer. If
+    /// not all data that is read could be written, the position of the file is
+    /// moved back such that the unsent bytes are sent by the next call.
+    #[cfg(not(target_os = "linux"))]
+    pub(crate) fn send_file(
+        &self,
+        file: &mut File,
+        size: usize,
+    ) -> io::Result<usize> {
+        use std::io::{Seek, SeekFrom, Write};
+
+        let mut buffer = vec![0; min(size, SEND_FILE_BUFFER_SIZE)];
+        let read = file.read(&mut buffer)?;
+
+        match (&self.inner).write(&buffer[0..read]) {
+            Ok(written) => {
+                if written < read {
+                    file.seek(SeekFrom::Current(-((read - written) as i64)))?;
+                }
+
+                Ok(written)
+            }
+            Err(err) => {
+                file.seek(SeekFrom::Current(-(read as i64)))?;
+                Err(err)
+            }
+        }
+    }
+
     pub(crate) fn try_clone(&self) -> io::Result<Socket> {
         let sock = Socket {
             inner: self.inner.try_clone()?,
@@ -407,4 +494,59 @@ mod tests {
         assert_eq!(slice.len(), 2);
         assert_eq!(buf.capacity(), 7);
     }
+
+    #[test]
+    fn test_send_file() {
+        let (raw1, raw2) =
+            RawSocket::pair(Domain::UNIX, Type::STREAM, None).unwrap();
+        let sender = Socket {
+            inner: raw1,
+            registered: AtomicI8::new(NOT_REGISTERED),
+            unix: true,
+        };
+        let receiver = Socket {
+            inner: raw2,
+            registered: AtomicI8::new(NOT_REGISTERED),
+            unix: true,
+        };
+        let path = std::env::temp_dir()
+            .join(format!("inko_test_send_file_{}", std::process::id()));
+
+        std::fs::write(&path, b"hello world").unwrap();
+
+        let mut file = File::open(&path).unwrap();
+        let mut buffer = Vec::new();
+
+        assert_eq!(sender.send_file(&mut file, 5).unwrap(), 5);
+        assert_eq!(sender.send_file(&mut file, 64).unwrap(), 6);
+        assert_eq!(sender.send_file(&mut file, 64).unwrap(), 0);
+        assert_eq!(receiver.read(&mut buffer, 64).unwrap(), 11);
+        assert_eq!(buffer, b"hello world");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_send_vectored() {
+        let (raw1, raw2) =
+            RawSocket::pair(Domain::UNIX, Type::STREAM, None).unwrap();
+        let sender = Socket {
+            inner: raw1,
+            registered: AtomicI8::new(NOT_REGISTERED),
+            unix: true,
+        };
+        let receiver = Socket {
+            inner: raw2,
+            registered: AtomicI8::new(NOT_REGISTERED),
+            unix: true,
+        };
+        let buffers: [&[u8]; 3] = [b"hello", b" ", b"world"];
+        let mut buffer = Vec::new();
+
+        assert_eq!(sender.send_vectored(&buffers, 0).unwrap(), 11);
+        assert_eq!(sender.send_vectored(&buffers, 6).unwrap(), 5);
+        assert_eq!(sender.send_vectored(&buffers, 11).unwrap(), 0);
+        assert_eq!(receiver.read(&mut buffer, 64).unwrap(), 16);
+        assert_eq!(buffer, b"hello worldworld");
+    }
 }
@@ -363,6 +363,14 @@ impl TypeParameterId {
         None
     }
 
+    pub fn method_names(self, db: &Database) -> Vec<String> {
+        self.get(db)
+            .requirements
+            .iter()
+            .flat_map(|req| req.method_names(db))
+            .collect()
+    }
+
     pub fn set_original(self, db: &mut Database, parameter: TypeParameterId) {
         self.get_mut(db).original = Some(parameter);
     }
@@ -658,6 +666,22 @@ impl TraitId {
         None
     }
 
+    pub fn method_names(self, db: &Database) -> Vec<String> {
+        let typ = self.get(db);
+        let mut names: Vec<String> = typ
+            .default_methods
+            .keys()
+            .chain(typ.required_methods.keys())
+            .cloned()
+            .collect();
+
+        for &req in &typ.required_traits {
+            names.append(&mut req.method_names(db));
+        }
+
+        names
+    }
+
     pub fn add_default_method(
         self,
         db: &mut Database,
@@ -824,6 +848,10 @@ impl TraitInstance {
         self.instance_of.method(db, name)
     }
 
+    pub fn method_names(self, db: &Database) -> Vec<String> {
+        self.instance_of.method_names(db)
+    }
+
     fn named_type(self, db: &Database, name: &str) -> Option<Symbol> {
         self.instance_of.named_type(db, name)
     }
@@ -1396,6 +1424,10 @@ impl ClassId {
         self.get(db).methods.get(name).cloned()
     }
 
+    pub fn method_names(self, db: &Database) -> Vec<String> {
+        self.get(db).methods.keys().cloned().collect()
+    }
+
     pub fn method_exists(self, db: &Database, name: &str) -> bool {
         self.get(db).methods.contains_key(name)
     }
@@ -1683,7 +1715,13 @@ impl Arguments {
         variable: VariableId,
     ) {
         let index = self.mapping.len();
-        let arg = Argument { index, name: name.clone(), value_type, variable };
+        let arg = Argument {
+            index,
+            name: name.clone(),
+            value_type,
+            variable,
+            default: None,
+        };
 
         self.mapping.insert(name, arg);
     }
@@ -1708,6 +1746,22 @@ pub struct Argument {
     pub name: String,
     pub value_type: TypeRef,
     pub variable: VariableId,
+
+    /// The value to use if the argument isn't specified at the call site.
+    pub default: Option<DefaultValue>,
+}
+
+/// The default value of a method argument.
+///
+/// Default values are limited to literals, such that they can be evaluated at
+/// the call site without depending on the scope of the method that defines
+/// them.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DefaultValue {
+    Int(i64),
+    Float(f64),
+    String(String),
+    Bool(bool),
 }
 
 /// A block of code, such as a closure or method.
@@ -1797,6 +1851,10 @@ pub enum BuiltinFunction {
     IntCheckedAdd,
     IntCheckedMul,
     IntCheckedSub,
+    IntCountOnes,
+    IntLeadingZeros,
+    IntTrailingZeros,
+    IntSwapBytes,
 }
 
 impl BuiltinFunction {
@@ -1840,6 +1898,10 @@ impl BuiltinFunction {
             BuiltinFunction::IntCheckedAdd,
             BuiltinFunction::IntCheckedMul,
             BuiltinFunction::IntCheckedSub,
+            BuiltinFunction::IntCountOnes,
+            BuiltinFunction::IntLeadingZeros,
+            BuiltinFunction::IntTrailingZeros,
+            BuiltinFunction::IntSwapBytes,
             BuiltinFunction::Moved,
             BuiltinFunction::Panic,
             BuiltinFunction::StringConcat,
@@ -1895,6 +1957,10 @@ impl BuiltinFunction {
             BuiltinFunction::IntCheckedAdd => "int_checked_add",
             BuiltinFunction::IntCheckedMul => "int_checked_mul",
             BuiltinFunction::IntCheckedSub => "int_checked_sub",
+            BuiltinFunction::IntCountOnes => "int_count_ones",
+            BuiltinFunction::IntLeadingZeros => "int_leading_zeros",
+            BuiltinFunction::IntTrailingZeros => "int_trailing_zeros",
+            BuiltinFunction::IntSwapBytes => "int_swap_bytes",
             BuiltinFunction::Moved => "moved",
             BuiltinFunction::Panic => "panic",
             BuiltinFunction::StringConcat => "string_concat",
@@ -1949,6 +2015,10 @@ impl BuiltinFunction {
             BuiltinFunction::IntCheckedAdd => checked_result,
             BuiltinFunction::IntCheckedMul => checked_result,
             BuiltinFunction::IntCheckedSub => checked_result,
+            BuiltinFunction::IntCountOnes => TypeRef::int(),
+            BuiltinFunction::IntLeadingZeros => TypeRef::int(),
+            BuiltinFunction::IntTrailingZeros => TypeRef::int(),
+            BuiltinFunction::IntSwapBytes => TypeRef::int(),
             BuiltinFunction::Moved => TypeRef::nil(),
             BuiltinFunction::Panic => TypeRef::Never,
             BuiltinFunction::StringConcat => TypeRef::string(),
@@ -2073,6 +2143,9 @@ pub struct Method {
     /// class' type parameters, if any. For instance methods, we only include
     /// the shapes of the method's type parameters.
     shapes: Vec<Shape>,
+
+    /// The location of the method's definition, if known.
+    location: Option<VariableLocation>,
 }
 
 impl Method {
@@ -2109,6 +2182,7 @@ impl Method {
             variadic: false,
             specializations: HashMap::new(),
             shapes: Vec::new(),
+            location: None,
         };
 
         db.methods.push(method);
@@ -2150,6 +2224,14 @@ impl MethodId {
         self.get_mut(db).receiver = receiver;
     }
 
+    pub fn set_location(self, db: &mut Database, location: VariableLocation) {
+        self.get_mut(db).location = Some(location);
+    }
+
+    pub fn location(self, db: &Database) -> Option<VariableLocation> {
+        self.get(db).location
+    }
+
     pub fn receiver_for_class_instance(
         self,
         db: &Database,
@@ -2285,6 +2367,15 @@ impl MethodId {
         arg.variable.set_value_type(db, variable_type);
     }
 
+    pub fn set_argument_default(
+        self,
+        db: &mut Database,
+        index: usize,
+        value: DefaultValue,
+    ) {
+        self.get_mut(db).arguments.mapping[index].default = Some(value);
+    }
+
     pub fn named_argument(
         self,
         db: &Database,
@@ -2743,6 +2834,15 @@ pub struct Module {
     constants: Vec<ConstantId>,
     symbols: HashMap<String, Symbol>,
     extern_methods: HashMap<String, MethodId>,
+
+    /// The symbols re-exported by this module.
+    ///
+    /// The keys are the names the symbols are exported as, the values the
+    /// modules the symbols are imported from and their names in those modules.
+    reexports: HashMap<String, (ModuleId, String)>,
+
+    /// The paths of the files embedded into this module's constants.
+    embedded_files: Vec<PathBuf>,
 }
 
 impl Module {
@@ -2771,6 +2871,8 @@ impl Module {
             constants: Vec::new(),
             symbols: HashMap::default(),
             extern_methods: HashMap::new(),
+            reexports: HashMap::new(),
+            embedded_files: Vec::new(),
         });
         id
     }
@@ -2796,6 +2898,14 @@ impl ModuleId {
         self.get(db).file.clone()
     }
 
+    pub fn add_embedded_file(self, db: &mut Database, path: PathBuf) {
+        self.get_mut(db).embedded_files.push(path);
+    }
+
+    pub fn embedded_files(self, db: &Database) -> &[PathBuf] {
+        &self.get(db).embedded_files
+    }
+
     pub fn symbol(self, db: &Database, name: &str) -> Option<Symbol> {
         self.get(db).symbols.get(name).cloned()
     }
@@ -2812,7 +2922,41 @@ impl ModuleId {
         self.get(db).symbols.contains_key(name)
     }
 
+    pub fn add_reexport(
+        self,
+        db: &mut Database,
+        name: String,
+        source: ModuleId,
+        source_name: String,
+    ) {
+        self.get_mut(db).reexports.insert(name, (source, source_name));
+    }
+
     pub fn import_symbol(self, db: &Database, name: &str) -> Option<Symbol> {
+        let mut module = self;
+        let mut name = name;
+        let mut steps = 0;
+
+        // Re-exported symbols may themselves be re-exported, so we follow the
+        // chain until we reach the module that defines the symbol. A module
+        // can't re-export more symbols than there are modules, so we use that
+        // to stop at cyclic re-exports.
+        while let Some((source, source_name)) =
+            module.get(db).reexports.get(name)
+        {
+            if steps == db.modules.len() {
+                return None;
+            }
+
+            module = *source;
+            name = source_name;
+            steps += 1;
+        }
+
+        module.defined_symbol(db, name)
+    }
+
+    fn defined_symbol(self, db: &Database, name: &str) -> Option<Symbol> {
         let symbol = self.symbol(db, name)?;
         let module_id = match symbol {
             Symbol::Class(id) => id.module(db),
@@ -4466,6 +4610,24 @@ impl TypeId {
         }
     }
 
+    /// Returns the names of all the methods that can be looked up on this
+    /// type.
+    ///
+    /// This is used to produce suggestions when a method lookup fails.
+    pub fn method_names(self, db: &Database) -> Vec<String> {
+        match self {
+            TypeId::Class(id) => id.method_names(db),
+            TypeId::Trait(id) => id.method_names(db),
+            TypeId::Module(id) => id.get(db).class.method_names(db),
+            TypeId::ClassInstance(id) => id.instance_of.method_names(db),
+            TypeId::TraitInstance(id) => id.method_names(db),
+            TypeId::TypeParameter(id) | TypeId::RigidTypeParameter(id) => {
+                id.method_names(db)
+            }
+            _ => Vec::new(),
+        }
+    }
+
     pub fn use_dynamic_dispatch(self) -> bool {
         matches!(
             self,
@@ -4623,6 +4785,38 @@ impl Database {
         self.module_mapping.get(name).cloned()
     }
 
+    /// Returns the modules that define a public class or trait with the given
+    /// name, sorted by their module names.
+    pub fn modules_defining_type(&self, name: &str) -> Vec<ModuleId> {
+        let mut ids: Vec<ModuleId> = (0..self.modules.len())
+            .map(|idx| ModuleId(idx as u32))
+            .filter(|id| {
+                matches!(
+                    id.defined_symbol(self, name),
+                    Some(sym @ (Symbol::Class(_) | Symbol::Trait(_)))
+                        if sym.is_public(self)
+                )
+            })
+            .collect();
+
+        ids.sort_by(|a, b| a.name(self).as_str().cmp(b.name(self).as_str()));
+        ids
+    }
+
+    /// Returns the modules of which the last component of the name equals the
+    /// given name (e.g. `std.string` for `string`), sorted by their names.
+    pub fn modules_named(&self, name: &str) -> Vec<ModuleId> {
+        let mut ids: Vec<ModuleId> = self
+            .module_mapping
+            .iter()
+            .filter(|(full, _)| full.rsplit('.').next() == Some(name))
+            .map(|(_, &id)| id)
+            .collect();
+
+        ids.sort_by(|a, b| a.name(self).as_str().cmp(b.name(self).as_str()));
+        ids
+    }
+
     pub fn class_in_module(&self, module: &str, name: &str) -> ClassId {
         if let Some(Symbol::Class(id)) = self.module(module).symbol(self, name)
         {
@@ -5094,6 +5288,22 @@ mod tests {
         assert_eq!(id.file(&db), PathBuf::from("test.inko"));
     }
 
+    #[test]
+    fn test_module_id_embedded_files() {
+        let mut db = Database::new();
+        let id = Module::alloc(
+            &mut db,
+            ModuleName::new("foo"),
+            PathBuf::from("test.inko"),
+        );
+
+        assert!(id.embedded_files(&db).is_empty());
+
+        id.add_embedded_file(&mut db, PathBuf::from("foo.txt"));
+
+        assert_eq!(id.embedded_files(&db), &[PathBuf::from("foo.txt")]);
+    }
+
     #[test]
     fn test_module_id_symbol() {
         let mut db = Database::new();
@@ -5176,6 +5386,26 @@ mod tests {
         assert_eq!(foo.import_symbol(&db, "fizz"), None);
     }
 
+    #[test]
+    fn test_module_id_import_symbol_with_reexports() {
+        let mut db = Database::new();
+        let foo = new_module(&mut db, "foo");
+        let bar = new_module(&mut db, "bar");
+        let baz = new_module(&mut db, "baz");
+        let class = new_class(&mut db, "A");
+
+        class.set_module(&mut db, foo);
+        foo.new_symbol(&mut db, "A".to_string(), Symbol::Class(class));
+        bar.add_reexport(&mut db, "B".to_string(), foo, "A".to_string());
+        baz.add_reexport(&mut db, "C".to_string(), bar, "B".to_string());
+        baz.add_reexport(&mut db, "D".to_string(), baz, "D".to_string());
+
+        assert_eq!(bar.import_symbol(&db, "B"), Some(Symbol::Class(class)));
+        assert_eq!(bar.import_symbol(&db, "A"), None);
+        assert_eq!(baz.import_symbol(&db, "C"), Some(Symbol::Class(class)));
+        assert_eq!(baz.import_symbol(&db, "D"), None);
+    }
+
     #[test]
     fn test_module_id_symbols() {
         let mut db = Database::new();
@@ -5339,6 +5569,53 @@ mod tests {
         assert!(block.named_type(&db, "T").is_none());
     }
 
+    #[test]
+    fn test_type_id_method_names() {
+        let mut db = Database::new();
+        let to_foo = new_trait(&mut db, "ToFoo");
+        let to_bar = new_trait(&mut db, "ToBar");
+        let foo = new_class(&mut db, "Foo");
+        let param = TypeParameter::alloc(&mut db, "T".to_string());
+        let to_foo_ins = TraitInstance::new(to_foo);
+        let m1 = Method::alloc(
+            &mut db,
+            ModuleId(0),
+            "to_foo".to_string(),
+            Visibility::Public,
+            MethodKind::Instance,
+        );
+        let m2 = Method::alloc(
+            &mut db,
+            ModuleId(0),
+            "to_bar".to_string(),
+            Visibility::Public,
+            MethodKind::Instance,
+        );
+
+        to_foo.add_required_method(&mut db, "to_foo".to_string(), m1);
+        to_bar.add_default_method(&mut db, "to_bar".to_string(), m2);
+        to_bar.add_required_trait(&mut db, to_foo_ins);
+        foo.add_method(&mut db, "to_foo".to_string(), m1);
+        param.add_requirements(&mut db, vec![TraitInstance::new(to_bar)]);
+
+        let mut names = TypeId::TypeParameter(param).method_names(&db);
+
+        names.sort();
+
+        assert_eq!(
+            TypeId::Class(foo).method_names(&db),
+            vec!["to_foo".to_string()]
+        );
+        assert_eq!(
+            TypeId::TraitInstance(to_foo_ins).method_names(&db),
+            vec!["to_foo".to_string()]
+        );
+        assert_eq!(names, vec!["to_bar".to_string(), "to_foo".to_string()]);
+        assert!(TypeId::Closure(Closure::alloc(&mut db, false))
+            .method_names(&db)
+            .is_empty());
+    }
+
     #[test]
     fn test_database_new() {
         let db = Database::new();
@@ -5362,6 +5639,42 @@ mod tests {
         assert_eq!(db.module("foo"), id);
     }
 
+    #[test]
+    fn test_database_modules_defining_type() {
+        let mut db = Database::new();
+        let foo = new_module(&mut db, "foo");
+        let bar = new_module(&mut db, "bar");
+        let baz = new_module(&mut db, "baz");
+        let class1 = new_class(&mut db, "Thing");
+        let class2 = Class::alloc(
+            &mut db,
+            "Thing".to_string(),
+            ClassKind::Regular,
+            Visibility::Private,
+            baz,
+        );
+
+        class1.get_mut(&mut db).module = foo;
+        foo.new_symbol(&mut db, "Thing".to_string(), Symbol::Class(class1));
+        bar.new_symbol(&mut db, "Thing".to_string(), Symbol::Class(class1));
+        baz.new_symbol(&mut db, "Thing".to_string(), Symbol::Class(class2));
+
+        assert_eq!(db.modules_defining_type("Thing"), vec![foo]);
+        assert!(db.modules_defining_type("Other").is_empty());
+    }
+
+    #[test]
+    fn test_database_modules_named() {
+        let mut db = Database::new();
+        let foo = new_module(&mut db, "std.foo");
+        let bar = new_module(&mut db, "bar.foo");
+
+        new_module(&mut db, "std.foo.baz");
+
+        assert_eq!(db.modules_named("foo"), vec![bar, foo]);
+        assert!(db.modules_named("quix").is_empty());
+    }
+
     #[test]
     #[should_panic]
     fn test_database_invalid_module() {